@@ -1,12 +1,18 @@
-use std::cell::LazyCell;
-use std::collections::HashMap;
-use std::path::Path;
-use std::fs;
+//! Module for creating array slice macro calls of MjModel and MjData arrays.
+use std::sync::LazyLock;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use inflector::Inflector;
-use regex::{self, Regex};
+use regex::Regex;
 
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::{Field, Ir};
+use crate::object_id::referenced_id_type;
+use crate::template::{self, Context, Template};
 
-const C_TO_RUST_TYPE_MAPPING: LazyCell<HashMap<&'static str, &'static str>> = LazyCell::new(
+
+static C_TO_RUST_TYPE_MAPPING: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(
     || HashMap::from_iter([
         ("float", "f32"),
         ("double", "f64"),
@@ -17,38 +23,90 @@ const C_TO_RUST_TYPE_MAPPING: LazyCell<HashMap<&'static str, &'static str>> = La
     ])
 );
 
-const ENUM_REGEX: LazyCell<Regex> = LazyCell::new(|| Regex::new(r"(.*?)\((mjt[A-z]+)\)").unwrap());
+static ENUM_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(.*?)\((mjt[A-z]+)\)").unwrap());
+static ATTR_TIMES_LEN_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.*)\(([A-z]+)\s*(?:x|\*)\s*(\w+)\)$").unwrap());
+static LEN_TIMES_ATTR_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.*)\(([0-9]*)\s*(?:\*|x)\s*(\w+)\)$").unwrap());
+static ATTR_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.*)\((\w+)\)$").unwrap());
+static SHAPE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.*)\(\s*(\w+(?:\s+x\s+\w+)+)\s*\)$").unwrap());
+static SUMMED_LEN_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.*)\(\s*([0-9]+)\s*[*x]\s*sum\((\w+)\)\s*\)$").unwrap());
+static DIM_SEPARATOR_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s*\bx\b\s*").unwrap());
+
+/// Generic view types, used by the multi-dimensional array slice entries.
+/// Arrays are stored row-major, thus the last dimension is contiguous.
+const ND_VIEW_TYPES: &str = include_str!("../templates/nd_view_types.rs");
+
+
+/// Checks whether the dimension `dim` is only known at runtime,
+/// i.e., it is some model/data attribute (e.g., nv) and not a literal or a constant (e.g., mjNREF).
+fn is_runtime_dim(dim: &str) -> bool {
+    dim.starts_with(|c: char| c.is_ascii_lowercase()) && !dim.starts_with("mj")
+}
+
+
+/// The default template, writing the array_slice_dyn! entries.
+const DEFAULT_TEMPLATE: &str = include_str!("../templates/array_slice.tpl");
 
 
-fn display_parsed(datatype: &str, name: &str, comment: &str, size_var: &str, size_mul: &str, accessor_prefix: &str, summed_type: bool) {
-    // Convert to pascal case whenever the type starts with 'mj' or try to map to a Rust type.
-    // If conversion fails, use the C FFI type.
-    let datatype = if datatype.starts_with("mj") {datatype.to_pascal_case()} else {
+/// Converts the C type to the Rust type: Pascal case whenever the type starts with 'mj', otherwise
+/// the mapped Rust type. If there's no mapping, the C FFI type is used.
+fn rust_type(datatype: &str) -> String {
+    if datatype.starts_with("mj") {datatype.to_pascal_case()} else {
         if let Some(rust_type) = C_TO_RUST_TYPE_MAPPING.get(datatype) {
             rust_type.to_string()
         } else { format!("std::ffi::c_{datatype}") }
-    };
+    }
+}
+
+
+/// Returns `true` if the Rust type is an enum or a typed id, whose values need to be cast.
+fn needs_cast(datatype: &str) -> bool {
+    datatype.starts_with("Mjt") && datatype != "MjtNum" && datatype != "MjtByte" || datatype.ends_with("Id")
+}
+
+
+/// Creates the template variables of an entry, whose length is the attribute `size_var`
+/// (multiplied by `size_mul` for summed lengths and arrays of fixed-size items).
+fn parsed_entry(datatype: String, name: &str, comment: &str, size_var: &str, size_mul: &str, accessor_prefix: &str, summed_type: bool) -> Context {
+    let entry = Context::new()
+        .text("name", name)
+        .text("doc", comment)
+        .text("length", format!("{accessor_prefix}.{size_var}"));
 
     // A special case where the length of an array is a sum of values in some other array
     if summed_type {
-        println!("{name}: &[{datatype}; \"{comment}\"; [{size_mul}; ({accessor_prefix}.{size_var}); ({accessor_prefix}.)]],");
+        entry.text("type", datatype).text("multiplier", size_mul).text("item_size", "").flag("cast", false)
     }
 
     // Create an array type if size is larger than one, otherwise assume scalar
-    else if size_mul == "1" || size_mul.len() == 0 {
-        println!(
-            "{name}: &[{datatype}{}; \"{comment}\"; {accessor_prefix}.{size_var}],",
-            if datatype.starts_with("Mjt") && datatype != "MjtNum" && datatype != "MjtByte" {" [cast]"} else {""}
-        );
+    else if size_mul == "1" || size_mul.is_empty() {
+        let cast = needs_cast(&datatype);
+        entry.text("type", datatype).text("multiplier", "").text("item_size", "").flag("cast", cast)
     }
     else {
         let type_cast = if size_mul.chars().all(char::is_numeric) {
             ""
         } else {" as usize"};
-        println!("{name}: &[[{datatype}; {size_mul}{type_cast}] [cast]; \"{comment}\"; {accessor_prefix}.{size_var}],");
+        entry.text("type", datatype).text("multiplier", "").text("item_size", format!("{size_mul}{type_cast}")).flag("cast", true)
     }
 }
 
+/// Creates the template variables of a multi-dimensional array, whose shape contains at least one dimension
+/// other than the first, that is only known at runtime.
+fn parsed_entry_nd(datatype: String, name: &str, comment: &str, dims: &[&str], accessor_prefix: &str) -> Context {
+    let shape = dims.iter().map(|dim|
+        if is_runtime_dim(dim) { format!("{accessor_prefix}.{dim}") }
+        else if dim.chars().all(char::is_numeric) { dim.to_string() }
+        else { format!("{dim} as usize") }
+    ).collect::<Vec<_>>().join(", ");
+
+    Context::new()
+        .text("name", name)
+        .text("doc", comment)
+        .flag("cast", needs_cast(&datatype))
+        .text("type", datatype)
+        .text("shape", shape)
+}
+
 /// Try to extract an enum type from the documentation string.
 /// E.g., dynamics type (mjtDyn) gets converted to ("dynamics type", "mjtDyn").
 fn extract_possible_enum<'a>(docstring: &'a str, datatype: &'a str) -> (&'a str, &'a str) {
@@ -62,49 +120,130 @@ fn extract_possible_enum<'a>(docstring: &'a str, datatype: &'a str) -> (&'a str,
 }
 
 
-pub fn create_array_slice(structs_filepath: &Path, accessor_prefix: &str, struct_name: &str) {
-    let filedata = fs::read_to_string(structs_filepath).unwrap();
-    // Match the correct struct
-    let re = regex::Regex::new(&format!(r"(?s)struct\s+{struct_name}\s*\{{.*?\n}};")).unwrap();
-    let struct_data = re.find(&filedata).expect("failed to find struct or struct body").as_str();
-
-    println!("------------------------------------------------");
-    println!("Processing lengths obtained via single attribute");
-    println!("------------------------------------------------");
-
-    // Match the sizes that are marked with n something x some number
-    let re = regex::Regex::new(r"(?m)((?:unsigned\s+)?[^\s]+)(?:\*\s+([^\s]+)|\s+([^\s]+)\[.+\]);\s+//(.*)\(([A-z]+)\s*(?:x|\*)\s*(\w+)\)$").unwrap();
-    for capture in re.captures_iter(struct_data) {
-        let (_, [mut datatype, name, mut comment, size_var, size_mul]) = capture.extract();
-        (comment, datatype) = extract_possible_enum(comment, datatype);
-        display_parsed(datatype, name, comment.trim(), size_var, size_mul, accessor_prefix, false);
-    }
-
-    // Match the sizes that are marked with some number * n something
-    let re = regex::Regex::new(r"(?m)((?:unsigned\s+)?[^\s]+)(?:\*\s+([^\s]+)|\s+([^\s]+)\[.+\]);\s+//(.*)\(([0-9]*)\s*(?:\*|x)\s*(\w+)\)$").unwrap();
-    for capture in re.captures_iter(struct_data) {
-        let (_, [mut datatype, name, mut comment, size_mul, size_var]) = capture.extract();
-        (comment, datatype) = extract_possible_enum(comment, datatype);
-        display_parsed(datatype, name, comment.trim(), size_var, size_mul, accessor_prefix, false);
-    }
+/// Generates array slice macro entries for the pointer and array fields of a struct,
+/// whose lengths are described in the field comments (e.g., `// body mass (nbody x 1)`).
+///
+/// The output is rendered by the `template` (by default templates/array_slice.tpl) with the lists
+/// `single` (lengths given by a single attribute), `nd` (multi-dimensional arrays with runtime shape) and
+/// `summed` (lengths given by the sum of a length array). Their items have the variables `name`, `type`,
+/// `doc` and the flag `cast` (the type needs a cast), along with:
+/// - `single`: `length` (e.g., ffi().nbody) and `item_size` (the length of array items, empty for scalars),
+/// - `nd`: `shape` (e.g., ffi().nkey, ffi().nq),
+/// - `summed`: `length` (the summed length array) and `multiplier`.
+///
+/// The template also has `accessor_prefix`, the flag `view_types` and `view_type_definitions`.
+pub struct ArraySliceGenerator {
+    /// The C name of the struct (e.g., mjModel_).
+    pub struct_name: String,
+    /// The prefix of the length attributes (e.g., ffi()).
+    pub accessor_prefix: String,
+    /// Also create the generic multi-dimensional view types.
+    pub view_types: bool,
+    /// Generate the ids referencing other objects as typed ids (see [`crate::object_id`]).
+    pub typed_ids: bool,
+    /// The output template, replacing the default one.
+    pub template: Option<Template>,
+}
 
-    // Match the sizes that are marked with some fixed attribute for length
-    let re = regex::Regex::new(r"(?m)((?:unsigned\s+)?[^\s]+)(?:\*\s+([^\s]+)|\s+([^\s]+)\[\w*\]);\s+//(.*)\((\w+)\)$").unwrap();
-    for capture in re.captures_iter(struct_data) {
-        let (_, [mut datatype, name, mut comment, size_var]) = capture.extract();
-        (comment, datatype) = extract_possible_enum(comment, datatype);
-        display_parsed(datatype, name, comment.trim(), size_var, "", accessor_prefix, false);
+impl ArraySliceGenerator {
+    /// Converts the C type of the field to the Rust type. With `typed_ids`, the `int` fields
+    /// referencing other objects have the type `BodyId`, etc.
+    fn field_type(&self, field: &Field, datatype: &str) -> String {
+        let (prefix, attribute) = field.name.split_once('_').unwrap_or(("", &field.name));
+        match referenced_id_type(prefix, attribute, &field.comment) {
+            Some(id_type) if self.typed_ids && datatype == "int" => id_type.to_string(),
+            _ => rust_type(datatype),
+        }
     }
+}
 
-    println!("--------------------------------------------------------");
-    println!("Processing lengths obtained via sum of some length array");
-    println!("--------------------------------------------------------");
-
-    // Match summed length array
-    let re = regex::Regex::new(r"(?m)((?:unsigned\s+)?[^\s]+)(?:\*\s+([^\s]+));\s+//(.*)\(\s*([0-9])+\s*[*x]\s*sum\((\w+)\)\s*\)$").unwrap();
-    for capture in re.captures_iter(struct_data) {
-        let (_, [mut datatype, name, mut comment, size_mul, size_var]) = capture.extract();
-        (comment, datatype) = extract_possible_enum(comment, datatype);
-        display_parsed(datatype, name, comment.trim(), size_var, size_mul, accessor_prefix, true);
+impl Generator for ArraySliceGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let struct_ = ir.find_struct(&self.struct_name).ok_or_else(|| Error::MissingStruct(self.struct_name.clone()))?;
+        let accessor_prefix = self.accessor_prefix.as_str();
+
+        // Only pointers and arrays have a length, which is written at the end of the comment
+        let mut fields = vec![];
+        for field in struct_.flat_fields() {
+            if field.pointer_depth == 1 && field.array_dims.is_empty() || field.pointer_depth == 0 && !field.array_dims.is_empty() {
+                fields.push(field);
+            }
+            else if field.is_pointer() {
+                report.skip("field", format!("{}.{}", struct_.name, field.name), "pointer to pointer or array of pointers");
+            }
+        }
+        let mut generated = HashSet::new();
+        let (mut single, mut nd, mut summed) = (vec![], vec![], vec![]);
+
+        // Match the sizes that are marked with n something x some number
+        for field in &fields {
+            let Some(capture) = ATTR_TIMES_LEN_REGEX.captures(&field.comment) else { continue };
+            let (_, [comment, size_var, size_mul]) = capture.extract();
+            if is_runtime_dim(size_mul) {  // processed as a multi-dimensional array
+                continue;
+            }
+            let (comment, datatype) = extract_possible_enum(comment, &field.c_type);
+            single.push(parsed_entry(self.field_type(field, datatype), &field.name, comment.trim(), size_var, size_mul, accessor_prefix, false));
+            generated.insert(&field.name);
+        }
+
+        // Match the sizes that are marked with some number * n something
+        for field in &fields {
+            let Some(capture) = LEN_TIMES_ATTR_REGEX.captures(&field.comment) else { continue };
+            let (_, [comment, size_mul, size_var]) = capture.extract();
+            let (comment, datatype) = extract_possible_enum(comment, &field.c_type);
+            single.push(parsed_entry(self.field_type(field, datatype), &field.name, comment.trim(), size_var, size_mul, accessor_prefix, false));
+            generated.insert(&field.name);
+        }
+
+        // Match the sizes that are marked with some fixed attribute for length
+        for field in &fields {
+            if !field.is_pointer() && !field.array_dims[0].chars().all(|c| c.is_alphanumeric() || c == '_') {
+                continue;
+            }
+            let Some(capture) = ATTR_REGEX.captures(&field.comment) else { continue };
+            let (_, [comment, size_var]) = capture.extract();
+            let (comment, datatype) = extract_possible_enum(comment, &field.c_type);
+            single.push(parsed_entry(self.field_type(field, datatype), &field.name, comment.trim(), size_var, "", accessor_prefix, false));
+            generated.insert(&field.name);
+        }
+
+        // Match the sizes that are marked with n something x n something else (x ...)
+        for field in &fields {
+            let Some(capture) = SHAPE_REGEX.captures(&field.comment) else { continue };
+            let (_, [comment, shape]) = capture.extract();
+            let dims: Vec<_> = DIM_SEPARATOR_REGEX.split(shape).collect();
+            if !dims[1..].iter().any(|dim| is_runtime_dim(dim)) {  // fixed inner dimensions are handled above
+                continue;
+            }
+            let (comment, datatype) = extract_possible_enum(comment, &field.c_type);
+            nd.push(parsed_entry_nd(self.field_type(field, datatype), &field.name, comment.trim(), &dims, accessor_prefix));
+            generated.insert(&field.name);
+        }
+
+        // Match summed length array
+        for field in fields.iter().filter(|f| f.is_pointer()) {
+            let Some(capture) = SUMMED_LEN_REGEX.captures(&field.comment) else { continue };
+            let (_, [comment, size_mul, size_var]) = capture.extract();
+            let (comment, datatype) = extract_possible_enum(comment, &field.c_type);
+            summed.push(parsed_entry(rust_type(datatype), &field.name, comment.trim(), size_var, size_mul, accessor_prefix, true));
+            generated.insert(&field.name);
+        }
+
+        for field in fields.iter().filter(|f| !generated.contains(&f.name)) {
+            let reason = if field.comment.is_empty() { "undocumented" } else { "length not recognized in the comment" };
+            report.skip("field", format!("{}.{}", struct_.name, field.name), reason);
+        }
+
+        let context = Context::new()
+            .list("single", single)
+            .list("nd", nd)
+            .list("summed", summed)
+            .text("accessor_prefix", accessor_prefix)
+            .flag("view_types", self.view_types)
+            .text("view_type_definitions", ND_VIEW_TYPES.trim_end());
+        write!(out, "{}", template::render_or_default(self.template.as_ref(), "templates/array_slice.tpl", DEFAULT_TEMPLATE, &context)?)?;
+        Ok(())
     }
 }
+
@@ -0,0 +1,172 @@
+//! Module for running the generators from a build script (build.rs).
+//! The headers are read from the MuJoCo include directory that is linked against, so the generated
+//! code always matches it, and the generated files are written into `OUT_DIR`.
+use std::ffi::OsString;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::diagnostic::{Diagnostic, Source};
+use crate::generator::{self, Generator};
+use crate::ir::Ir;
+use crate::{introspect, parse};
+
+
+/// An additional input file, which is not a header of the include directory.
+pub enum Input {
+    /// A C header (e.g., structs.h from MuJoCo's documentation).
+    Header(PathBuf),
+    /// The indexer_xmacro.h file, defining the view groups.
+    ViewGroups(PathBuf),
+    /// The APItypes.rst documentation file.
+    TypeDocs(PathBuf),
+    /// An introspect file of MuJoCo's Python bindings (e.g., python/mujoco/introspect/functions.py).
+    Introspect(PathBuf),
+}
+
+
+/// Errors that can occur in the build script.
+#[derive(Debug)]
+pub enum Error {
+    /// The `OUT_DIR` environment variable is not set, i.e., not running from a build script.
+    MissingOutDir,
+    /// An input file or the include directory could not be read.
+    Read(PathBuf, io::Error),
+    /// An output file could not be written.
+    Write(PathBuf, io::Error),
+    /// The generator of an output file failed.
+    Generate(String, generator::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingOutDir => write!(f, "OUT_DIR is not set"),
+            Error::Read(path, error) => write!(f, "could not read {}: {error}", path.display()),
+            Error::Write(path, error) => write!(f, "could not write {}: {error}", path.display()),
+            Error::Generate(file_name, error) => write!(f, "could not generate {file_name}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::MissingOutDir => None,
+            Error::Read(_, error) | Error::Write(_, error) => Some(error),
+            Error::Generate(_, error) => Some(error),
+        }
+    }
+}
+
+
+/// Runs the configured generators on MuJoCo's headers and writes each output into its own file.
+/// ```no_run
+/// use mujoco_rs_utils::build_script::BuildScript;
+/// use mujoco_rs_utils::fixed_arr_fn::FixedArrayFnGenerator;
+///
+/// // In build.rs, after the include directory has been discovered:
+/// BuildScript::new("mujoco/include")
+///     .output("fixed_arr_fn.rs", FixedArrayFnGenerator::default())
+///     .run().unwrap();
+/// // In the crate: include!(concat!(env!("OUT_DIR"), "/fixed_arr_fn.rs"));
+/// ```
+pub struct BuildScript {
+    include_dir: PathBuf,
+    /// The output directory, [`None`] for `OUT_DIR`.
+    out_dir: Option<PathBuf>,
+    inputs: Vec<Input>,
+    outputs: Vec<(String, Box<dyn Generator>)>,
+}
+
+impl BuildScript {
+    /// Creates a build script helper, which parses all the headers of `include_dir`
+    /// (the directory containing mujoco/mujoco.h or the mujoco directory itself) and writes into `OUT_DIR`.
+    pub fn new(include_dir: impl Into<PathBuf>) -> Self {
+        Self { include_dir: include_dir.into(), out_dir: None, inputs: vec![], outputs: vec![] }
+    }
+
+    /// Writes into `out_dir` instead of `OUT_DIR`.
+    pub fn out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(out_dir.into());
+        self
+    }
+
+    /// Adds an additional input file.
+    pub fn input(mut self, input: Input) -> Self {
+        self.inputs.push(input);
+        self
+    }
+
+    /// Adds an output file, named `file_name`, created by the `generator`.
+    pub fn output(mut self, file_name: impl Into<String>, generator: impl Generator + 'static) -> Self {
+        self.outputs.push((file_name.into(), Box::new(generator)));
+        self
+    }
+
+    /// Parses the inputs, runs the generators and writes the outputs, returning the paths of the written files.
+    /// `cargo:rerun-if-changed` is printed for each consumed file and `cargo:warning` for each parse diagnostic.
+    /// Files whose content did not change are not rewritten, to avoid needless recompilation.
+    /// Fails when no output directory was given and `OUT_DIR` is not set.
+    pub fn run(&self) -> Result<Vec<PathBuf>, Error> {
+        self.run_with_env(|key| env::var_os(key))
+    }
+
+    /// Like [`run`](Self::run), but looks up the environment variables (i.e., `OUT_DIR`) with `env_var`.
+    pub fn run_with_env(&self, env_var: impl Fn(&str) -> Option<OsString>) -> Result<Vec<PathBuf>, Error> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => env_var("OUT_DIR").ok_or(Error::MissingOutDir)?.into(),
+        };
+        let ir = self.parse()?;
+        let mut written = vec![];
+        for (file_name, generator) in &self.outputs {
+            let code = generator.generate_string(&ir).map_err(|e| Error::Generate(file_name.clone(), e))?;
+            let path = out_dir.join(file_name);
+            if fs::read_to_string(&path).ok().as_deref() != Some(code.as_str()) {
+                fs::write(&path, code).map_err(|e| Error::Write(path.clone(), e))?;
+            }
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Parses the headers of the include directory (in the order of file names) and the additional inputs.
+    fn parse(&self) -> Result<Ir, Error> {
+        let nested_dir = self.include_dir.join("mujoco");
+        let header_dir = if nested_dir.is_dir() { nested_dir } else { self.include_dir.clone() };
+        let mut headers = fs::read_dir(&header_dir).map_err(|e| Error::Read(header_dir.clone(), e))?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>().map_err(|e| Error::Read(header_dir.clone(), e))?;
+        headers.retain(|path| path.extension().is_some_and(|e| e == "h"));
+        headers.sort();
+        println!("cargo:rerun-if-changed={}", header_dir.display());  // rerun when headers are added or removed
+
+        let mut ir = Ir::default();
+        let mut diagnostics = vec![];
+        for header in &headers {
+            ir.extend(parse::parse_header(&read_input(header)?, &mut diagnostics));
+        }
+
+        for input in &self.inputs {
+            match input {
+                Input::Header(path) => ir.extend(parse::parse_header(&read_input(path)?, &mut diagnostics)),
+                Input::ViewGroups(path) => ir.view_groups.extend(parse::parse_view_groups(&read_input(path)?, &mut diagnostics)),
+                Input::TypeDocs(path) => ir.type_docs.extend(parse::parse_type_docs(&read_input(path)?)),
+                Input::Introspect(path) => ir.extend(introspect::parse_introspect(&read_input(path)?, &mut diagnostics)),
+            }
+        }
+
+        for Diagnostic { severity, message, path, line, column, .. } in &diagnostics {
+            println!("cargo:warning={severity}: {message} ({}:{line}:{column})", path.display());
+        }
+        Ok(ir)
+    }
+}
+
+
+/// Reads the input file and tells cargo to rerun the build script when it changes.
+fn read_input(path: &Path) -> Result<Source, Error> {
+    println!("cargo:rerun-if-changed={}", path.display());
+    Source::read(path).map_err(|e| Error::Read(path.to_path_buf(), e))
+}
@@ -0,0 +1,157 @@
+//! Module for generating builder types of MuJoCo's parameter structs (e.g., mjOption, mjvCamera).
+use std::io::Write;
+use inflector::Inflector;
+
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::getter_setter::{convert_type, extract_possible_enum};
+use crate::ir::{Field, Ir};
+use crate::layout_check::{bindgen_name, rust_ident};
+
+
+/// Creates the builder methods of `fields`, which write into `self.inner`.
+/// Undocumented fields, pointers and multi-dimensional arrays of the struct `ffi_name`
+/// are skipped and recorded into `report`.
+fn builder_methods(fields: &[Field], ffi_name: &str, builder_name: &str, report: &mut Report) -> (Vec<String>, Vec<String>) {
+    let mut methods = vec![];
+    let mut nested_builders = vec![];
+    for field in fields {
+        let Field { name, c_type, comment, .. } = field;
+        if let Some(nested) = &field.nested {
+            let nested_name = format!("{}{}Builder", builder_name.trim_end_matches("Builder"), name.to_pascal_case());
+            methods.push(format!(
+"    /// Configures `{name}`: {comment}.
+    pub fn {name}(mut self, f: impl FnOnce({nested_name}) -> {nested_name}) -> Self {{
+        self.inner.{name} = f({nested_name} {{ inner: self.inner.{name} }}).inner;
+        self
+    }}"));
+            let (nested_methods, nested_nested) = builder_methods(nested, c_type, &nested_name, report);
+            nested_builders.push(format!(
+"/// Builder for the `{name}` part of the parent struct.
+#[derive(Debug, Clone, Copy)]
+pub struct {nested_name} {{
+    inner: mujoco_c::{c_type},
+}}
+
+impl {nested_name} {{
+{}
+}}", nested_methods.join("\n\n")));
+            nested_builders.extend(nested_nested);
+        }
+        else if comment.is_empty() {
+            report.skip("field", format!("{ffi_name}.{name}"), "undocumented");
+        }
+        else if field.is_pointer() {
+            report.skip("field", format!("{ffi_name}.{name}"), "pointer");
+        }
+        else if let Some(size) = field.array_len() {
+            let (comment, datatype, _) = extract_possible_enum(comment, c_type);
+            let (comment, datatype) = (comment.trim(), convert_type(datatype));
+            let type_cast = if size.chars().all(char::is_numeric) { "" } else { " as usize" };
+            methods.push(format!(
+"    /// Sets `{name}`: {comment}.
+    pub fn {name}(mut self, value: [{datatype}; {size}{type_cast}]) -> Self {{
+        self.inner.{name} = value;
+        self
+    }}"));
+        }
+        else if !field.array_dims.is_empty() {
+            report.skip("field", format!("{ffi_name}.{name}"), "multi-dimensional array");
+        }
+        else {
+            let (comment, enum_type, was_enum) = extract_possible_enum(comment, c_type);
+            let (comment, datatype) = (comment.trim(), convert_type(c_type));
+            let method = rust_ident(name);
+            let member = bindgen_name(name);
+            let (arg_type, cast) = if was_enum {
+                (convert_type(enum_type), format!(" as {datatype}"))
+            } else {
+                (datatype, String::new())
+            };
+            methods.push(format!(
+"    /// Sets `{name}`: {comment}.
+    pub fn {method}(mut self, value: {arg_type}) -> Self {{
+        self.inner.{member} = value{cast};
+        self
+    }}"));
+        }
+    }
+    (methods, nested_builders)
+}
+
+
+/// Generates a builder type for a MuJoCo struct (e.g., mjOption_), including builders of its nested structs.
+pub struct BuilderGenerator {
+    /// The C name of the struct (e.g., mjOption_).
+    pub struct_name: String,
+    /// The MuJoCo function that fills the struct with default values (e.g., mj_defaultOption).
+    /// When [`None`], the struct is zero-initialized.
+    pub default_fn: Option<String>,
+}
+
+impl Generator for BuilderGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let struct_ = ir.find_struct(&self.struct_name).ok_or_else(|| Error::MissingStruct(self.struct_name.clone()))?;
+        create_builder(&struct_.fields, &self.struct_name, self.default_fn.as_deref(), out, report)
+    }
+}
+
+
+fn create_builder(
+    fields: &[Field], struct_name: &str, default_fn: Option<&str>, out: &mut dyn Write, report: &mut Report
+) -> Result<(), Error> {
+    let ffi_name = struct_name.trim_end_matches('_');
+    let type_name = ffi_name.to_pascal_case();
+    let builder_name = format!("{type_name}Builder");
+    let (methods, nested_builders) = builder_methods(fields, struct_name, &builder_name, report);
+
+    // Start with MuJoCo's defaults if a default function is given, otherwise zero everything.
+    let (new_doc, new_body) = if let Some(default_fn) = default_fn {
+        (format!("initialized to MuJoCo's default values (`{default_fn}`)"), format!("let mut inner = std::mem::MaybeUninit::uninit();
+        unsafe {{
+            mujoco_c::{default_fn}(inner.as_mut_ptr());
+            Self {{ inner: inner.assume_init() }}
+        }}"))
+    } else {
+        ("zero-initialized".to_string(), "Self { inner: unsafe { std::mem::zeroed() } }".to_string())
+    };
+
+    writeln!(
+        out,
+"/// Builder for [`{type_name}`].
+#[derive(Debug, Clone, Copy)]
+pub struct {builder_name} {{
+    inner: {type_name},
+}}
+
+impl {builder_name} {{
+    /// Creates a new builder, {new_doc}.
+    pub fn new() -> Self {{
+        {new_body}
+    }}
+
+{}
+
+    /// Builds the [`{type_name}`].
+    pub fn build(self) -> {type_name} {{
+        self.inner
+    }}
+}}
+
+impl Default for {builder_name} {{
+    fn default() -> Self {{
+        Self::new()
+    }}
+}}
+
+impl From<{type_name}> for {builder_name} {{
+    fn from(inner: {type_name}) -> Self {{
+        Self {{ inner }}
+    }}
+}}", methods.join("\n\n"))?;
+
+    for nested_builder in nested_builders {
+        writeln!(out, "\n{nested_builder}")?;
+    }
+    Ok(())
+}
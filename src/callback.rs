@@ -0,0 +1,148 @@
+//! Module for creating safe setters of MuJoCo's global callbacks (e.g., mjcb_control and mju_user_error).
+//! Each setter stores the Rust closure and installs an `extern "C"` trampoline, which reconstructs the
+//! references from the raw pointers and calls the closure. Panics must not unwind into MuJoCo,
+//! so a panicking closure aborts the process.
+//!
+//! The closures receive the raw `mujoco_c::mjModel` and `mjData` rather than the wrappers of mujoco-rs:
+//! the wrappers own their pointer and free it on drop, while the callbacks only borrow the model and data
+//! MuJoCo is computing with, thus they can't be wrapped without taking ownership.
+use std::io::Write;
+use inflector::Inflector;
+
+use crate::fixed_arr_fn::doc_comment;
+use crate::generator::{Error, Generator};
+use crate::ir::{CallbackType, Function, Ir, Param};
+use crate::report::Report;
+
+
+/// The callbacks, which MuJoCo doesn't expect to return (e.g., mju_error continues in an invalid state
+/// after its handler returns).
+const NON_RETURNING_CALLBACKS: &[&str] = &["mju_user_error"];
+
+
+/// A parameter of a callback, as received by the trampoline and passed to the closure.
+struct CallbackParam {
+    name: String,
+    /// The type of the trampoline's parameter, as in the raw bindings.
+    raw_type: String,
+    /// The type of the closure's parameter.
+    rust_type: String,
+    /// The expression converting the raw parameter into the closure's argument.
+    argument: String,
+}
+
+
+/// Converts a parameter of a callback or returns the reason, why it can't be converted.
+fn process_param(param: &Param) -> Result<CallbackParam, String> {
+    let name = param.name.to_snake_case();
+    let (raw_type, rust_type, argument) = match (param.c_type.as_str(), param.pointer_depth, param.is_const) {
+        // The model and data are the ones MuJoCo is computing with, valid for the duration of the call.
+        // They're borrowed, thus passed as the raw structs instead of the owning wrappers of mujoco-rs
+        (c_type @ ("mjModel" | "mjData"), 1, true) => {
+            (format!("*const mujoco_c::{c_type}"), format!("&mujoco_c::{c_type}"), format!("unsafe {{ &*{name} }}"))
+        }
+        (c_type @ ("mjModel" | "mjData"), 1, false) => {
+            (format!("*mut mujoco_c::{c_type}"), format!("&mut mujoco_c::{c_type}"), format!("unsafe {{ &mut *{name} }}"))
+        }
+        ("char", 1, true) => (
+            "*const std::ffi::c_char".into(), "&str".into(),
+            format!("&unsafe {{ std::ffi::CStr::from_ptr({name}) }}.to_string_lossy()")
+        ),
+        (c_type, 0, _) if param.array_len.is_none() && c_type != "void" => {
+            let rust_type = if c_type.starts_with("mj") { c_type.to_pascal_case() } else { format!("std::ffi::c_{c_type}") };
+            (rust_type.clone(), rust_type, name.clone())
+        }
+        _ => return Err(format!("parameter {} has an unsupported type ({})", param.name, param.c_type)),
+    };
+    Ok(CallbackParam { name, raw_type, rust_type, argument })
+}
+
+
+/// Generates setters of the global callbacks, which take Rust closures, and the `extern "C"` trampolines calling them.
+pub struct CallbackGenerator;
+
+impl Generator for CallbackGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        writeln!(out, "use std::panic::{{catch_unwind, AssertUnwindSafe}};\nuse std::sync::{{Arc, PoisonError, RwLock}};")?;
+        for callback in &ir.callbacks {
+            let name = callback.name.as_str();
+            let Some(signature) = ir.callback_signature(callback) else {
+                report.skip("callback", name, "the function pointer type is not declared in the headers");
+                continue;
+            };
+            let Function { return_type, params, .. } = signature;
+            if return_type.ends_with('*') {
+                report.skip("callback", name, "returns a pointer");
+                continue;
+            }
+            let params = match params.iter().map(process_param).collect::<Result<Vec<_>, _>>() {
+                Ok(params) => params,
+                Err(reason) => {
+                    report.skip("callback", name, reason);
+                    continue;
+                }
+            };
+
+            let return_type_out = match return_type.as_str() {
+                "void" => String::new(),
+                _ if return_type.starts_with("mj") => format!(" -> {}", return_type.to_pascal_case()),
+                _ => format!(" -> std::ffi::c_{return_type}"),
+            };
+            let unset = if return_type_out.is_empty() { "return" } else { "return Default::default()" };
+            let closure_type = format!(
+                "Fn({}){return_type_out} + Send + Sync", params.iter().map(|p| p.rust_type.as_str()).collect::<Vec<_>>().join(", ")
+            );
+            let raw_params = params.iter().map(|p| format!("{}: {}", p.name, p.raw_type)).collect::<Vec<_>>().join(", ");
+            let arguments = params.iter().map(|p| p.argument.as_str()).collect::<Vec<_>>().join(", ");
+            let storage = name.to_uppercase();
+            let non_returning = NON_RETURNING_CALLBACKS.contains(&name);
+            let call = if non_returning {
+                format!("// MuJoCo doesn't expect the callback to return, thus returning also aborts the process
+    let _ = catch_unwind(AssertUnwindSafe(|| callback({arguments})));
+    std::process::abort()")
+            } else {
+                format!("// Unwinding into MuJoCo is undefined behavior, thus a panic aborts the process
+    match catch_unwind(AssertUnwindSafe(|| callback({arguments}))) {{
+        Ok(result) => result,
+        Err(_) => std::process::abort(),
+    }}")
+            };
+            let return_doc = if non_returning {
+                format!("\n/// MuJoCo doesn't expect `{name}` to return, thus the process is also aborted when the closure returns\n/// (e.g., after logging the message).")
+            } else { String::new() };
+            let raw_doc = if params.iter().any(|p| p.rust_type.starts_with("&mujoco_c::") || p.rust_type.starts_with("&mut mujoco_c::")) {
+                "\n/// The model and data are MuJoCo's raw structs, borrowed for the duration of the call."
+            } else { "" };
+            let type_doc = match &callback.signature {
+                CallbackType::Named(type_name) if !signature.doc.is_empty() => {
+                    format!("\n///\n/// `{type_name}`:{}", doc_comment(signature).trim_start_matches("///"))
+                }
+                _ => String::new(),
+            };
+
+            writeln!(out, "
+/// The closure called by [`{name}_trampoline`].
+static {storage}: RwLock<Option<Arc<dyn {closure_type}>>> = RwLock::new(None);
+
+unsafe extern \"C\" fn {name}_trampoline({raw_params}){return_type_out} {{
+    let callback = {storage}.read().unwrap_or_else(PoisonError::into_inner).clone();
+    let Some(callback) = callback else {{ {unset} }};
+    {call}
+}}
+
+/// Sets the global callback `{name}` to the closure, replacing the previous one.
+/// A panic of the closure aborts the process, as it can't unwind through MuJoCo.{return_doc}{raw_doc}{type_doc}
+pub fn set_{name}(callback: impl {closure_type} + 'static) {{
+    *{storage}.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(callback));
+    unsafe {{ mujoco_c::{name} = Some({name}_trampoline) }};
+}}
+
+/// Removes the global callback `{name}`.
+pub fn clear_{name}() {{
+    unsafe {{ mujoco_c::{name} = None }};
+    *{storage}.write().unwrap_or_else(PoisonError::into_inner) = None;
+}}")?;
+        }
+        Ok(())
+    }
+}
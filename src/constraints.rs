@@ -0,0 +1,184 @@
+//! Module for collecting valid ranges and default values of struct fields, used
+//! for generating validating setters and tables of default values.
+//! The metadata is collected from the field comments, the XML reference documentation
+//! (XMLreference.rst) and a side-car override file, with later sources taking precedence.
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use regex::Regex;
+
+use crate::diagnostic::{Diagnostic, Source};
+
+
+static COMMENT_CONSTRAINT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:^|[\s(])(>=|<=|>|<)\s*(-?[0-9][0-9.eE+-]*)").unwrap());
+static XML_AT_VAL_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#":at-val:`[^`]*?"([^"]*)"`"#).unwrap());
+
+/// Phrases used by the XML reference to describe ranges, mapped to the corresponding constraint.
+const XML_RANGE_PHRASES: [(&str, &str, &str); 4] = [
+    ("must be positive", ">", "0"),
+    ("must be strictly positive", ">", "0"),
+    ("must be non-negative", ">=", "0"),
+    ("must be nonnegative", ">=", "0"),
+];
+
+
+/// A comparison the value of a field must satisfy, e.g., `> 0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    pub op: String,
+    pub value: String,
+}
+
+/// Collected metadata of a single field.
+#[derive(Debug, Clone, Default)]
+pub struct FieldMeta {
+    pub constraints: Vec<Constraint>,
+    /// The default value as written in the source, with array elements separated by whitespace.
+    pub default: Option<String>,
+}
+
+impl FieldMeta {
+    /// Adds a constraint, replacing any existing constraint with the same operator.
+    fn add_constraint(&mut self, op: &str, value: &str) {
+        self.constraints.retain(|c| c.op != op);
+        self.constraints.push(Constraint { op: op.to_string(), value: value.to_string() });
+    }
+}
+
+
+/// Extracts constraints written in the field comment (e.g., "timestep (> 0)").
+pub fn constraints_from_comment(comment: &str) -> Vec<Constraint> {
+    COMMENT_CONSTRAINT_REGEX.captures_iter(comment).map(|capture| {
+        let (_, [op, value]) = capture.extract();
+        Constraint { op: op.to_string(), value: value.to_string() }
+    }).collect()
+}
+
+
+/// Collects defaults and ranges of attributes of the `element` (e.g., option) in the XML reference.
+fn collect_xml_reference(filedata: &str, element: &str, metadata: &mut HashMap<String, FieldMeta>) {
+    let re = Regex::new(&format!(r"(?m)^\.\. _{element}-(\w+):\s*$")).unwrap();
+    let anchors: Vec<_> = re.captures_iter(filedata).collect();
+    for (i, capture) in anchors.iter().enumerate() {
+        let attribute = capture.get(1).unwrap().as_str();
+        // The attribute's documentation lasts until the next anchor of the element or the end of file.
+        let start = capture.get(0).unwrap().end();
+        let end = anchors.get(i + 1).map_or(filedata.len(), |next| next.get(0).unwrap().start());
+        let section = &filedata[start..end];
+        // Don't continue into documentation of other elements.
+        let section = section.find("\n.. _").map_or(section, |pos| &section[..pos]);
+
+        let meta = metadata.entry(attribute.to_string()).or_default();
+        if let Some(default) = XML_AT_VAL_REGEX.captures(section) {
+            meta.default = Some(default.get(1).unwrap().as_str().trim().to_string());
+        }
+
+        let description = section.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        for (phrase, op, value) in XML_RANGE_PHRASES {
+            if description.contains(phrase) {
+                meta.add_constraint(op, value);
+            }
+        }
+    }
+}
+
+
+/// Collects constraints and defaults from the override file.
+/// Each non-empty line that is not a comment (starting with #) is in one of the formats:
+/// - `<field> <op> <value>`, where `<op>` is one of `>`, `>=`, `<`, `<=`,
+/// - `<field> default <value>`, where array values are separated by whitespace.
+///
+/// The field is its name or its path through nested structs (e.g., map.haze).
+///
+/// Invalid lines are reported and ignored.
+fn collect_overrides(source: &Source, metadata: &mut HashMap<String, FieldMeta>, diagnostics: &mut Vec<Diagnostic>) {
+    for line in source.text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let mut parts = line.split_whitespace();
+        let (Some(field), Some(op)) = (parts.next(), parts.next()) else {
+            diagnostics.push(source.warning(line, "invalid override, expected `<field> <op> <value>`, ignoring it"));
+            continue;
+        };
+        let value = parts.collect::<Vec<_>>().join(" ");
+        let meta = metadata.entry(field.to_string()).or_default();
+        match op {
+            "default" => meta.default = Some(value),
+            ">" | ">=" | "<" | "<=" => meta.add_constraint(op, &value),
+            _ => diagnostics.push(source.warning(op, format!("unknown override operator `{op}`, ignoring the line"))),
+        }
+    }
+}
+
+
+/// Collects metadata from the XML reference (limited to attributes of `xml_element`) and the override file.
+/// Comment constraints are handled by the caller, as they are parsed per field.
+pub fn collect_metadata(
+    xml_reference: Option<&Source>, xml_element: &str, overrides: Option<&Source>, diagnostics: &mut Vec<Diagnostic>
+) -> HashMap<String, FieldMeta> {
+    let mut metadata = HashMap::new();
+    if let Some(source) = xml_reference {
+        collect_xml_reference(&source.text, xml_element, &mut metadata);
+    }
+
+    if let Some(source) = overrides {
+        collect_overrides(source, &mut metadata, diagnostics);
+    }
+    metadata
+}
+
+
+/// The range of each integer type, in which the values of fields of the type must be.
+const INTEGER_RANGES: [(&[&str], i128, i128); 6] = [
+    (&["MjtByte", "u8", "std::ffi::c_uchar"], u8::MIN as i128, u8::MAX as i128),
+    (&["i8", "std::ffi::c_char", "std::ffi::c_schar"], i8::MIN as i128, i8::MAX as i128),
+    (&["i32", "std::ffi::c_int"], i32::MIN as i128, i32::MAX as i128),
+    (&["u32", "std::ffi::c_uint"], u32::MIN as i128, u32::MAX as i128),
+    (&["i64", "std::ffi::c_longlong"], i64::MIN as i128, i64::MAX as i128),
+    (&["u64", "std::ffi::c_ulonglong"], u64::MIN as i128, u64::MAX as i128),
+];
+
+
+/// Converts a numeric `value` to a Rust literal of the `datatype`, e.g., `.5` to `0.5` and `inf` to `f64::INFINITY`.
+/// Returns the reason as an error when the value is not numeric (e.g., an enum keyword)
+/// or out of the range of the `datatype`.
+pub fn numeric_literal(value: &str, datatype: &str) -> Result<String, String> {
+    let float_type = match datatype {
+        "f32" | "std::ffi::c_float" => Some("f32"),
+        "f64" | "MjtNum" | "std::ffi::c_double" => Some("f64"),
+        _ => None,
+    };
+    if let Some(float_type) = float_type {
+        let parsed: f64 = value.parse().map_err(|_| format!("\"{value}\" is not a number"))?;
+        if parsed.is_nan() {
+            return Ok(format!("{float_type}::NAN"));
+        }
+        if parsed.is_infinite() {
+            let sign = if parsed < 0.0 { "NEG_" } else { "" };
+            return Ok(format!("{float_type}::{sign}INFINITY"));
+        }
+        if float_type == "f32" && !value.parse::<f32>().is_ok_and(f32::is_finite) {
+            return Err(format!("{value} is out of the range of {datatype}"));
+        }
+        // Digits are needed on both sides of the decimal point (e.g., .5 and 5.)
+        let (sign, literal) = match value.trim_start_matches('+') {
+            literal if literal.starts_with('-') => ("-", &literal[1..]),
+            literal => ("", literal),
+        };
+        let (mantissa, exponent) = literal.find(['e', 'E']).map_or((literal, ""), |i| literal.split_at(i));
+        let mantissa = match mantissa.split_once('.') {
+            Some((integer, fraction)) => format!(
+                "{}.{}", if integer.is_empty() { "0" } else { integer }, if fraction.is_empty() { "0" } else { fraction }
+            ),
+            None if exponent.is_empty() => format!("{mantissa}.0"),
+            None => mantissa.to_string(),
+        };
+        Ok(format!("{sign}{mantissa}{exponent}"))
+    }
+    else {
+        let parsed: i128 = value.parse().map_err(|_| format!("\"{value}\" is not an integer"))?;
+        let (_, min, max) = INTEGER_RANGES.iter().find(|(types, ..)| types.contains(&datatype))
+            .ok_or_else(|| format!("the range of {datatype} is unknown"))?;
+        if parsed < *min || parsed > *max {
+            return Err(format!("{value} is out of the range of {datatype}"));
+        }
+        Ok(parsed.to_string())
+    }
+}
@@ -0,0 +1,298 @@
+//! Module for reporting how much of MuJoCo's API is covered by a mujoco-rs checkout.
+//! The Rust sources of the checkout are scanned for calls of MuJoCo functions and for the entries
+//! of the (generated) macro calls, such as array_slice_dyn! and info_method!. Files generated by bindgen
+//! are scanned for the raw declarations instead.
+//! The scan is textual, so it's a heuristic: e.g., an entry name matches a field of any struct.
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::fs;
+
+use inflector::Inflector;
+use regex::Regex;
+
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::{Field, Ir};
+use crate::layout_check::bindgen_name;
+
+
+/// The header comment of the files generated by bindgen.
+const BINDGEN_MARKER: &str = "automatically generated by rust-bindgen";
+/// Macros of the standard library, whose arguments are not entries.
+const STD_MACROS: [&str; 16] = [
+    "assert", "assert_eq", "assert_ne", "debug_assert", "debug_assert_eq", "debug_assert_ne", "format", "matches",
+    "panic", "print", "println", "eprint", "eprintln", "vec", "write", "writeln"
+];
+
+static BINDGEN_FN_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"pub fn (\w+)\s*\(").unwrap());
+static BINDGEN_STRUCT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)pub struct (\w+)\s*\{(.*?)\n\}").unwrap());
+static BINDGEN_FIELD_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"pub (?:r#)?(\w+)\s*:").unwrap());
+static CALL_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"mujoco_c::(\w+)|\b(mj[a-z]*_\w+)\s*\(").unwrap());
+static MACRO_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(\w+)!\s*[({\[]").unwrap());
+static ENTRY_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:r#)?\b(\w+)\s*:(?:[^:]|$)").unwrap());
+static STRING_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#""(?:[^"\\]|\\.)*""#).unwrap());
+static COMMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"//[^\n]*").unwrap());
+
+
+/// What the Rust sources of a mujoco-rs checkout use and declare.
+#[derive(Debug, Clone, Default)]
+pub struct Usage {
+    /// Functions called outside of the bindings, directly or through `mujoco_c::`.
+    pub called: HashSet<String>,
+    /// Names of the entries of macro calls (e.g., body_pos of array_slice_dyn!).
+    pub macro_entries: HashSet<String>,
+    /// Entries of the info_method! and info_with_view! calls, by the lowercase class and item (e.g., model and body).
+    pub view_groups: HashMap<(String, String), HashSet<String>>,
+    /// Functions declared in the bindings.
+    pub bound_functions: HashSet<String>,
+    /// Fields declared in the bindings, by the struct name (e.g., mjModel_).
+    pub bound_fields: HashMap<String, HashSet<String>>,
+    /// The scanned files generated by bindgen.
+    pub bindings: Vec<PathBuf>,
+}
+
+impl Usage {
+    /// Scans all the Rust sources under `root`, skipping hidden directories and the target directory.
+    pub fn scan(root: &Path) -> io::Result<Self> {
+        let mut usage = Self::default();
+        let mut directories = vec![root.to_path_buf()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = fs::read_dir(&directory)?.map(|entry| entry.map(|e| e.path())).collect::<Result<Vec<_>, _>>()?;
+            entries.sort();
+            for path in entries {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                if path.is_dir() {
+                    if !file_name.starts_with('.') && file_name != "target" {
+                        directories.push(path);
+                    }
+                }
+                else if path.extension().is_some_and(|e| e == "rs") {
+                    let text = fs::read_to_string(&path)?;
+                    if text.contains(BINDGEN_MARKER) {
+                        usage.scan_bindings(&text);
+                        usage.bindings.push(path);
+                    }
+                    else {
+                        usage.scan_source(&text);
+                    }
+                }
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Records the functions and struct fields declared in a file generated by bindgen.
+    pub fn scan_bindings(&mut self, text: &str) {
+        self.bound_functions.extend(BINDGEN_FN_REGEX.captures_iter(text).map(|c| c[1].to_string()));
+        for capture in BINDGEN_STRUCT_REGEX.captures_iter(text) {
+            let (_, [name, body]) = capture.extract();
+            self.bound_fields.entry(name.to_string()).or_default()
+                .extend(BINDGEN_FIELD_REGEX.captures_iter(body).map(|c| c[1].to_string()));
+        }
+    }
+
+    /// Records the function calls and macro entries of a (non-generated) Rust source.
+    pub fn scan_source(&mut self, text: &str) {
+        // Strings and comments may contain anything, including colons
+        let text = STRING_REGEX.replace_all(text, "\"\"");
+        let text = COMMENT_REGEX.replace_all(&text, "");
+        self.called.extend(CALL_REGEX.captures_iter(&text).map(|c| c.get(1).or(c.get(2)).unwrap().as_str().to_string()));
+
+        for capture in MACRO_REGEX.captures_iter(&text) {
+            let macro_name = &capture[1];
+            if STD_MACROS.contains(&macro_name) {
+                continue;
+            }
+            let body = macro_body(&text[capture.get(0).unwrap().end()..]);
+            let entries: HashSet<_> = ENTRY_REGEX.captures_iter(body).map(|c| c[1].to_string()).collect();
+
+            // info_method! { Model, ffi(), body, [...], [...], [...] } and info_with_view!(Data, actuator, [...], [...])
+            let arguments: Vec<_> = body.split(',').map(str::trim).collect();
+            let group = match macro_name {
+                "info_method" if arguments.len() > 2 => Some((arguments[0], arguments[2])),
+                "info_with_view" if arguments.len() > 1 => Some((arguments[0], arguments[1])),
+                _ => None,
+            };
+            if let Some((class, item)) = group {
+                self.view_groups.entry((class.to_lowercase(), item.to_lowercase())).or_default().extend(entries.iter().cloned());
+            }
+            self.macro_entries.extend(entries);
+        }
+    }
+}
+
+
+/// Returns the body of a macro call, from after the opening bracket until the matching closing bracket.
+fn macro_body(text: &str) -> &str {
+    let mut depth = 1;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &text[..i];
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+
+/// How an item of MuJoCo's API is covered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Used by the safe wrappers (called or a macro entry).
+    Wrapped,
+    /// Only declared in the raw FFI bindings.
+    RawOnly,
+    /// Not even declared in the bindings.
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Wrapped => write!(f, "wrapped"),
+            Status::RawOnly => write!(f, "raw-only"),
+            Status::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+
+/// Items of a single category (e.g., functions), by section in the order of the headers.
+#[derive(Debug, Default)]
+struct Category {
+    sections: Vec<(String, Vec<(String, Status)>)>,
+}
+
+impl Category {
+    fn add(&mut self, section: &str, name: &str, status: Status) {
+        let section = if section.is_empty() { "(no section)" } else { section };
+        let index = match self.sections.iter().position(|(title, _)| title == section) {
+            Some(index) => index,
+            None => {
+                self.sections.push((section.to_string(), vec![]));
+                self.sections.len() - 1
+            }
+        };
+        self.sections[index].1.push((name.to_string(), status));
+    }
+}
+
+
+/// Creates a summary of the statuses, e.g., `3 of 4 wrapped (75.0%), 1 raw-only (25.0%), 0 missing (0.0%)`.
+fn summary<'a>(statuses: impl Iterator<Item = &'a Status>) -> String {
+    let statuses: Vec<_> = statuses.collect();
+    let total = statuses.len();
+    let count = |status| statuses.iter().filter(|s| ***s == status).count();
+    let percentage = |count: usize| if total == 0 { 0.0 } else { 100.0 * count as f64 / total as f64 };
+    let (wrapped, raw_only, missing) = (count(Status::Wrapped), count(Status::RawOnly), count(Status::Missing));
+    format!(
+        "{wrapped} of {total} wrapped ({:.1}%), {raw_only} raw-only ({:.1}%), {missing} missing ({:.1}%)",
+        percentage(wrapped), percentage(raw_only), percentage(missing)
+    )
+}
+
+
+/// Generates the coverage report of the functions, the mjModel and mjData fields and the view groups
+/// of the IR by the scanned mujoco-rs checkout.
+/// For each section, the raw-only and missing items are listed.
+pub struct CoverageGenerator {
+    pub usage: Usage,
+}
+
+impl CoverageGenerator {
+    /// Returns the status of a field of the struct `struct_name`, given the fields wrapped through view groups.
+    fn field_status(&self, struct_name: &str, field: &Field, view_fields: &HashSet<(String, String)>) -> Status {
+        if self.usage.macro_entries.contains(&field.name) || view_fields.contains(&(struct_name.to_string(), field.name.clone())) {
+            Status::Wrapped
+        }
+        else {
+            self.bound_status(struct_name, &field.name)
+        }
+    }
+
+    /// Returns the status of an unwrapped field, depending on whether it's declared in the bindings.
+    fn bound_status(&self, struct_name: &str, field_name: &str) -> Status {
+        let bound = self.usage.bound_fields.get(struct_name).is_some_and(|fields| fields.contains(&bindgen_name(field_name)));
+        if bound { Status::RawOnly } else { Status::Missing }
+    }
+}
+
+impl Generator for CoverageGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, _report: &mut Report) -> Result<(), Error> {
+        let mut functions = Category::default();
+        for function in &ir.functions {
+            let status = if self.usage.called.contains(&function.name) {
+                Status::Wrapped
+            }
+            else if self.usage.bound_functions.contains(&function.name) {
+                Status::RawOnly
+            }
+            else { Status::Missing };
+            functions.add(&function.section, &function.name, status);
+        }
+
+        // View groups (e.g., MJMODEL_BODY) are wrapped by info_method! calls, which wrap their listed entries
+        let mut view_groups = Category::default();
+        let mut view_fields = HashSet::new();
+        for view_group in &ir.view_groups {
+            let class = view_group.class.to_lowercase().to_pascal_case();
+            let struct_name = format!("mj{class}_");
+            let entry_fields: Vec<_> = view_group.entries.iter()
+                .map(|entry| (entry.attribute.trim_start_matches('_'), format!("{}{}", entry.prefix.trim(), entry.attribute)))
+                .collect();
+            let status = match self.usage.view_groups.get(&(class.to_lowercase(), view_group.item.to_lowercase())) {
+                Some(entries) => {
+                    for (attribute, field_name) in &entry_fields {
+                        if entries.contains(*attribute) {
+                            view_fields.insert((struct_name.clone(), field_name.clone()));
+                        }
+                    }
+                    Status::Wrapped
+                }
+                None if entry_fields.iter().all(|(_, field_name)| self.bound_status(&struct_name, field_name) == Status::RawOnly) => {
+                    Status::RawOnly
+                }
+                None => Status::Missing,
+            };
+            view_groups.add(&class, &format!("MJ{}_{}", view_group.class, view_group.item), status);
+        }
+
+        let mut categories = vec![("functions".to_string(), functions)];
+        for struct_name in ["mjModel_", "mjData_"] {
+            let Some(struct_) = ir.find_struct(struct_name) else { continue };
+            let mut fields = Category::default();
+            for field in &struct_.fields {
+                fields.add(&field.section, &field.name, self.field_status(struct_name, field, &view_fields));
+            }
+            categories.push((format!("{} fields", struct_name.trim_end_matches('_')), fields));
+        }
+        categories.push(("view groups".to_string(), view_groups));
+
+        for (i, (title, category)) in categories.iter().enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            let statuses = category.sections.iter().flat_map(|(_, items)| items.iter().map(|(_, status)| status));
+            writeln!(out, "{title}: {}", summary(statuses))?;
+            for (section, items) in &category.sections {
+                writeln!(out, "  {section}: {}", summary(items.iter().map(|(_, status)| status)))?;
+                for listed in [Status::RawOnly, Status::Missing] {
+                    let names: Vec<_> = items.iter().filter(|(_, status)| *status == listed).map(|(name, _)| name.as_str()).collect();
+                    if !names.is_empty() {
+                        writeln!(out, "    {listed}: {}", names.join(", "))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+//! Module for creating wrappers of the derivative (mjd_) functions. Their output matrices are optional
+//! (NULL skips an output) and their shapes depend on the model sizes, e.g., `A: (2*nv+na x 2*nv+na)`.
+//! The shapes are read from the documentation of the function, which can be corrected with a side-car
+//! override file. Known errors of the documentation are corrected by built-in overrides.
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::LazyLock;
+use inflector::Inflector;
+use regex::Regex;
+
+use crate::diagnostic::{Diagnostic, Source};
+use crate::fixed_arr_fn::doc_comment;
+use crate::generator::{Error, Generator};
+use crate::getter_setter::convert_type;
+use crate::ir::{Function, Ir, Param};
+use crate::report::Report;
+
+
+/// A documented output shape, e.g., `A: (2*nv+na x nu)`.
+static SHAPE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(\w+):\s*\(\s*(.+?)\s+x\s+(.+?)\s*\)\s*$").unwrap());
+static SHAPE_TOKEN_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s*(?:([A-Za-z_]\w*)|([0-9]+)|([-+*/()]))").unwrap());
+
+
+/// The shape of an output matrix, as expressions of the model sizes (e.g., `2*nv+na`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    pub rows: String,
+    pub cols: String,
+}
+
+/// Shapes of the outputs, by function and parameter name (e.g., (mjd_transitionFD, A)).
+pub type Shapes = HashMap<(String, String), Shape>;
+
+/// Corrections of the documented shapes (function, parameter, rows, cols), applied before the override file.
+/// The documentation of mjd_transitionFD swaps the shapes of C and D.
+const BUILTIN_SHAPE_OVERRIDES: [(&str, &str, &str, &str); 2] = [
+    ("mjd_transitionFD", "C", "nsensordata", "2*nv+na"),
+    ("mjd_transitionFD", "D", "nsensordata", "nu"),
+];
+
+
+/// Collects the shapes from the override file. Each non-empty line that is not a comment (starting with #)
+/// is in the format `<function> <parameter>: (<rows> x <cols>)`, e.g., `mjd_transitionFD C: (nsensordata x 2*nv+na)`.
+///
+/// Invalid lines are reported and ignored.
+pub fn parse_shape_overrides(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Shapes {
+    let mut shapes = Shapes::new();
+    for line in source.text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let shape = line.split_once(char::is_whitespace).and_then(|(function, shape)| Some((function, SHAPE_REGEX.captures(shape)?)));
+        let Some((function, capture)) = shape else {
+            diagnostics.push(source.warning(line, "invalid shape, expected `<function> <parameter>: (<rows> x <cols>)`, ignoring it"));
+            continue;
+        };
+        shapes.insert((function.to_string(), capture[1].to_string()), Shape { rows: capture[2].to_string(), cols: capture[3].to_string() });
+    }
+    shapes
+}
+
+
+/// Returns the shapes documented in the function comment (e.g., `//      A: (2*nv+na x 2*nv+na)`).
+fn documented_shapes(function: &Function) -> Vec<(String, Shape)> {
+    function.doc.iter().filter_map(|line| SHAPE_REGEX.captures(line)).map(|capture| {
+        (capture[1].to_string(), Shape { rows: capture[2].to_string(), cols: capture[3].to_string() })
+    }).collect()
+}
+
+
+/// Converts a size expression (e.g., `2*nv+na`) into Rust code reading the sizes from the model `model`.
+/// Returns [`None`] if the expression is not an arithmetic expression of model sizes (n...).
+fn size_expression(expression: &str, model: Option<&str>) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = expression;
+    while !rest.trim().is_empty() {
+        let capture = SHAPE_TOKEN_REGEX.captures(rest).filter(|c| c.get(0).unwrap().start() == 0)?;
+        if let Some(size) = capture.get(1) {
+            if !size.as_str().starts_with('n') {
+                return None;
+            }
+            out += &format!("{}.ffi().{}", model?, size.as_str());
+        }
+        else if let Some(number) = capture.get(2) {
+            out += number.as_str();
+        }
+        else {
+            match &capture[3] {
+                operator @ ("(" | ")") => out += operator,
+                operator => out += &format!(" {operator} "),
+            }
+        }
+        rest = &rest[capture.get(0).unwrap().end()..];
+    }
+    Some(out)
+}
+
+
+/// Returns the size expression in parentheses, unless it's a single size or number.
+fn parenthesized(expression: &str) -> String {
+    if expression.chars().all(|c| c.is_alphanumeric() || c == '_') { expression.to_string() } else { format!("({expression})") }
+}
+
+
+/// An output of a derivative function.
+struct Output {
+    name: String,
+    c_name: String,
+    length: Length,
+    rust_type: String,
+}
+
+/// The length of an output or input.
+enum Length {
+    /// A pointer to a matrix of the shape, whose length is the Rust `expression`.
+    Shape { expression: String, shape: Shape },
+    /// A fixed-size array of this length.
+    Fixed(String),
+}
+
+
+/// Rust parameters, call arguments, names of all the parameters, outputs and length checks of a wrapper,
+/// which is generic over the model of MjData when `generics` is not empty.
+struct Wrapper {
+    generics: &'static str,
+    parameters: Vec<String>,
+    arguments: Vec<String>,
+    names: Vec<String>,
+    outputs: Vec<Output>,
+    checks: Vec<String>,
+}
+
+
+/// Generates wrappers of the derivative (mjd_) functions, whose outputs are optional `&mut` buffers of the
+/// documented shapes, and `_alloc` variants, which allocate and return all the outputs.
+pub struct DerivativeGenerator {
+    /// Shapes replacing the documented ones (e.g., when the documentation is wrong).
+    pub shape_overrides: Shapes,
+}
+
+impl Generator for DerivativeGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        for function in ir.functions.iter().filter(|f| f.name.starts_with("mjd_")) {
+            let Wrapper { generics, parameters, arguments, names, outputs, checks } = match self.process_function(function) {
+                Ok(wrapper) => wrapper,
+                Err(reason) => {
+                    report.skip("function", &function.name, reason);
+                    continue;
+                }
+            };
+
+            let (fn_name, wrapper_name) = (function.name.as_str(), function.name.to_snake_case());
+            let lengths = outputs.iter().map(|output| match &output.length {
+                Length::Shape { shape, .. } => format!("/// - `{}`: {} ({} x {})", output.name, output.c_name, shape.rows, shape.cols),
+                Length::Fixed(array_len) => format!("/// - `{}`: {} ({array_len})", output.name, output.c_name),
+            }).collect::<Vec<_>>().join("\n");
+            let panics = if checks.is_empty() { "" } else { "\n///\n/// # Panics\n/// When the length of an output doesn't match its shape." };
+            let checks = checks.iter().map(|check| format!("    {check}\n")).collect::<String>();
+            writeln!(out, "
+{}
+///
+/// The outputs are optional (`None` skips computing them):
+{lengths}{panics}
+pub fn {wrapper_name}{generics}({}) {{
+{checks}    unsafe {{ mujoco_c::{fn_name}({}) }}
+}}", doc_comment(function), parameters.join(", "), arguments.join(", "))?;
+
+            // The allocating variant takes only the inputs
+            let inputs: Vec<_> = parameters.iter().filter(|p| !p.contains(": Option<&mut")).map(String::as_str).collect();
+            let call_arguments: Vec<_> = names.iter().map(|name| match outputs.iter().any(|o| o.name == *name) {
+                true => format!("Some(&mut {name})"),
+                false => name.clone(),
+            }).collect();
+            let (allocations, types): (Vec<_>, Vec<_>) = outputs.iter().map(|Output { name, length, rust_type, .. }| match length {
+                Length::Shape { expression, .. } => (format!("    let mut {name} = vec![0.0; {expression}];"), format!("Vec<{rust_type}>")),
+                Length::Fixed(array_len) => (format!("    let mut {name} = [0.0; {array_len}];"), format!("[{rust_type}; {array_len}]")),
+            }).unzip();
+            let output_names: Vec<_> = outputs.iter().map(|o| o.name.as_str()).collect();
+            let (return_type, returned) = match outputs.len() {
+                1 => (types[0].clone(), output_names[0].to_string()),
+                _ => (format!("({})", types.join(", ")), format!("({})", output_names.join(", "))),
+            };
+            writeln!(out, "
+/// Like [`{wrapper_name}`], but computes and returns all the outputs ({}).
+pub fn {wrapper_name}_alloc{generics}({}) -> {return_type} {{
+{}
+    {wrapper_name}({});
+    {returned}
+}}", output_names.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", "), inputs.join(", "),
+                allocations.join("\n"), call_arguments.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl DerivativeGenerator {
+    /// Converts the parameters of the function into the parts of the wrapper.
+    /// Returns the reason as an error, when the function can't be wrapped.
+    fn process_function(&self, function: &Function) -> Result<Wrapper, String> {
+        if function.return_type != "void" {
+            return Err(format!("returns {}", function.return_type));
+        }
+        let mut shapes = documented_shapes(function);
+        shapes.extend(BUILTIN_SHAPE_OVERRIDES.iter().filter(|(f, ..)| *f == function.name).map(|&(_, p, rows, cols)| {
+            (p.to_string(), Shape { rows: rows.to_string(), cols: cols.to_string() })
+        }));
+        shapes.extend(
+            self.shape_overrides.iter().filter(|((f, _), _)| *f == function.name).map(|((_, p), shape)| (p.clone(), shape.clone()))
+        );
+        let shape_of = |param: &Param| shapes.iter().rev().find(|(name, _)| *name == param.name).map(|(_, shape)| shape.clone());
+        let model = function.params.iter().find(|p| p.c_type == "mjModel" && p.is_pointer()).map(|p| p.name.to_snake_case());
+
+        let mut wrapper = Wrapper { generics: "", parameters: vec![], arguments: vec![], names: vec![], outputs: vec![], checks: vec![] };
+        for param in &function.params {
+            // Names may collide after the case conversion (e.g., the output D and the mjData d)
+            let mut name = param.name.to_snake_case();
+            if wrapper.names.contains(&name) {
+                name += "_out";
+            }
+            wrapper.names.push(name.clone());
+            let rust_type = convert_type(&param.c_type);
+
+            if matches!(param.c_type.as_str(), "mjModel" | "mjData") && param.pointer_depth == 1 {
+                let (reference, ffi) = if param.is_const { ("&", "ffi") } else { ("&mut ", "ffi_mut") };
+                // MjData is generic over the model it borrows
+                let rust_type = if param.c_type == "mjData" {
+                    wrapper.generics = "<M: Deref<Target = MjModel>>";
+                    format!("{rust_type}<M>")
+                } else { rust_type };
+                wrapper.parameters.push(format!("{name}: {reference}{rust_type}"));
+                wrapper.arguments.push(format!("{name}.{ffi}()"));
+                continue;
+            }
+            if !param.is_pointer() && param.array_len.is_none() {
+                wrapper.parameters.push(format!("{name}: {rust_type}"));
+                wrapper.arguments.push(name);
+                continue;
+            }
+
+            let length = match (&param.array_len, shape_of(param)) {
+                (Some(array_len), _) => Length::Fixed(array_len.clone()),
+                (None, Some(shape)) if param.pointer_depth == 1 => {
+                    let size = |expression: &str| size_expression(expression, model.as_deref()).map(|size| {
+                        if size.contains(' ') { format!("({size}) as usize") } else { format!("{size} as usize") }
+                    });
+                    let (Some(rows), Some(cols)) = (size(&shape.rows), size(&shape.cols)) else {
+                        return Err(format!("shape of {} is not in terms of model sizes", param.name));
+                    };
+                    Length::Shape { expression: format!("{rows} * {cols}"), shape }
+                }
+                _ => return Err(format!("parameter {} is a pointer of unknown length", param.name)),
+            };
+
+            let check = |condition: &str, shape: &Shape| format!(
+                "assert!({condition}, \"{name} must have {} x {} elements\");", parenthesized(&shape.rows), parenthesized(&shape.cols)
+            );
+            if param.is_const {
+                match &length {
+                    Length::Fixed(array_len) => wrapper.parameters.push(format!("{name}: &[{rust_type}; {array_len}]")),
+                    Length::Shape { expression, shape } => {
+                        wrapper.parameters.push(format!("{name}: &[{rust_type}]"));
+                        wrapper.checks.push(check(&format!("{name}.len() == {expression}"), shape));
+                    }
+                }
+                wrapper.arguments.push(format!("{name}.as_ptr()"));
+            }
+            else {
+                match &length {
+                    Length::Fixed(array_len) => wrapper.parameters.push(format!("{name}: Option<&mut [{rust_type}; {array_len}]>")),
+                    Length::Shape { expression, shape } => {
+                        wrapper.parameters.push(format!("{name}: Option<&mut [{rust_type}]>"));
+                        wrapper.checks.push(check(&format!("{name}.as_ref().is_none_or(|x| x.len() == {expression})"), shape));
+                    }
+                }
+                wrapper.arguments.push(format!("{name}.map_or(std::ptr::null_mut(), |x| x.as_mut_ptr())"));
+                wrapper.outputs.push(Output { name, c_name: param.name.clone(), length, rust_type });
+            }
+        }
+        if wrapper.outputs.is_empty() {
+            return Err("has no outputs".into());
+        }
+        Ok(wrapper)
+    }
+}
@@ -0,0 +1,99 @@
+//! Module for diagnostics about unparsable input, located in the source file and rendered like compiler diagnostics.
+//! Parsers report recoverable problems as diagnostics and skip the offending declaration, instead of panicking.
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+
+/// The contents of an input file, along with its path.
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+impl Source {
+    pub fn new(path: impl Into<PathBuf>, text: impl Into<String>) -> Self {
+        Self { path: path.into(), text: text.into() }
+    }
+
+    /// Reads the file at `path`.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        Ok(Self::new(path, fs::read_to_string(path)?))
+    }
+
+    /// Returns the byte range of `part`, which must be a sub-slice of the text.
+    pub fn span_of(&self, part: &str) -> Range<usize> {
+        let start = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        debug_assert!(start + part.len() <= self.text.len(), "part is not a sub-slice of the source");
+        start..start + part.len()
+    }
+
+    /// Creates a diagnostic about the `span` (byte range) of the text.
+    pub fn diagnostic(&self, severity: Severity, span: Range<usize>, message: impl Into<String>) -> Diagnostic {
+        let line_start = self.text[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[span.start..].find('\n').map_or(self.text.len(), |i| span.start + i);
+        let line = self.text[..span.start].matches('\n').count() + 1;
+        let column = self.text[line_start..span.start].chars().count() + 1;
+        let length = self.text[span.start..span.end.min(line_end)].chars().count().max(1);
+        Diagnostic {
+            severity, message: message.into(), path: self.path.clone(), line, column, length,
+            snippet: self.text[line_start..line_end].trim_end().to_string()
+        }
+    }
+
+    /// Creates a warning about `part`, which must be a sub-slice of the text.
+    pub fn warning(&self, part: &str, message: impl Into<String>) -> Diagnostic {
+        self.diagnostic(Severity::Warning, self.span_of(part), message)
+    }
+}
+
+
+/// Severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The input was not understood and was skipped.
+    Warning,
+    /// The input could not be processed.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+
+/// A problem found in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub path: PathBuf,
+    /// The line number (1-based).
+    pub line: usize,
+    /// The column (1-based, in characters).
+    pub column: usize,
+    /// The number of highlighted characters, starting at the column.
+    pub length: usize,
+    /// The whole source line.
+    pub snippet: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_number = self.line.to_string();
+        let padding = " ".repeat(line_number.len());
+        writeln!(f, "{}: {}", self.severity, self.message)?;
+        writeln!(f, "{padding}--> {}:{}:{}", self.path.display(), self.line, self.column)?;
+        writeln!(f, "{padding} |")?;
+        writeln!(f, "{line_number} | {}", self.snippet)?;
+        write!(f, "{padding} | {}{}", " ".repeat(self.column - 1), "^".repeat(self.length))
+    }
+}
@@ -0,0 +1,381 @@
+//! Module for previewing the changes of regenerated code: a unified diff of the old and new text,
+//! and a summary of the new, removed and changed items (e.g., fields of macro entries and functions).
+use std::fmt::{self, Display};
+use std::sync::LazyLock;
+use regex::Regex;
+
+
+static FUNCTION_REGEX: LazyLock<Regex> = LazyLock::new(
+    || Regex::new(r"^\s*(?:pub(?:\([\w:]+\))?\s+)?(?:const\s+)?(?:unsafe\s+)?fn\s+(\w+)").unwrap()
+);
+static TYPE_REGEX: LazyLock<Regex> = LazyLock::new(
+    || Regex::new(r"^\s*(?:pub(?:\([\w:]+\))?\s+)?(?:struct|enum|trait|type)\s+(\w+)").unwrap()
+);
+static IMPL_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^impl\b(?:<[^>]*>)?\s*(?:[\w:<>, ]+\s+for\s+)?(\w+)").unwrap());
+static MACRO_CALL_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(\w+!)\s*[({]\s*(\w+),\s*(?:ffi\(\),\s*)?(\w+)").unwrap());
+/// Macro entries (e.g., `body_pos: &[...]`) and struct fields (e.g., `    pub timestep: Option<MjtNum>,`).
+static FIELD_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(?:pub(?:\([\w:]+\))?\s+)?(?:r#)?(\w+):\s").unwrap());
+
+/// Number of unchanged lines shown around the changes.
+const CONTEXT_LINES: usize = 3;
+
+
+/// A line of the edit script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    /// Unchanged line (old index, new index).
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+
+/// Computes the shortest edit script transforming `old` into `new`, with Myers' algorithm in linear space.
+/// Between unchanged lines, the deletions come before the insertions.
+fn edits(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let mut script = vec![];
+    diff(old, new, 0, 0, &mut script);
+    for changes in script.split_mut(|e| matches!(e, Edit::Equal(..))) {
+        changes.sort_by_key(|e| matches!(e, Edit::Insert(_)));  // stable, thus keeps the order of the lines
+    }
+    script
+}
+
+
+/// Appends the edits transforming `a` into `b`, which start at the lines `x` and `y` of the old and new text.
+/// After skipping the common prefix and suffix, the middle snake of a shortest path splits the rest into
+/// two problems of about half the edits each, which are solved recursively.
+fn diff(a: &[&str], b: &[&str], x: usize, y: usize, script: &mut Vec<Edit>) {
+    let prefix = a.iter().zip(b).take_while(|(p, q)| p == q).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(p, q)| p == q).count();
+    script.extend((0..prefix).map(|i| Edit::Equal(x + i, y + i)));
+
+    let (a_rest, b_rest) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    let (x_rest, y_rest) = (x + prefix, y + prefix);
+    if a_rest.is_empty() {
+        script.extend((0..b_rest.len()).map(|j| Edit::Insert(y_rest + j)));
+    }
+    else if b_rest.is_empty() {
+        script.extend((0..a_rest.len()).map(|i| Edit::Delete(x_rest + i)));
+    }
+    else {
+        let ((x_start, y_start), (x_end, y_end)) = middle_snake(a_rest, b_rest);
+        diff(&a_rest[..x_start], &b_rest[..y_start], x_rest, y_rest, script);
+        script.extend((0..x_end - x_start).map(|i| Edit::Equal(x_rest + x_start + i, y_rest + y_start + i)));
+        diff(&a_rest[x_end..], &b_rest[y_end..], x_rest + x_end, y_rest + y_end, script);
+    }
+    script.extend((0..suffix).map(|i| Edit::Equal(x + a.len() - suffix + i, y + b.len() - suffix + i)));
+}
+
+
+/// Returns the start and end of the middle snake (a run of equal lines) of a shortest edit path from `a` to `b`,
+/// found by searching from both ends until the paths overlap. Only the furthest points of the current round
+/// are kept, thus the memory is linear in the number of lines.
+fn middle_snake(a: &[&str], b: &[&str]) -> ((usize, usize), (usize, usize)) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d + 1;
+    // The furthest x of each diagonal k = x - y, forward from the start and backward from the end
+    // (the backward x and y count from the ends, its diagonal delta - k is the forward diagonal k)
+    let (mut forward, mut backward) = (vec![0; 2 * offset as usize + 1], vec![0; 2 * offset as usize + 1]);
+    let furthest = |v: &[isize], d: isize, k: isize| {
+        let i = (k + offset) as usize;
+        if k == -d || k != d && v[i - 1] < v[i + 1] { v[i + 1] } else { v[i - 1] + 1 }
+    };
+
+    for d in 0..=max_d {
+        for k in (-d..=d).step_by(2) {
+            let mut x = furthest(&forward, d, k);
+            let (x_start, y_start) = (x, x - k);
+            while x < n && x - k < m && a[x as usize] == b[(x - k) as usize] {
+                x += 1;
+            }
+            forward[(k + offset) as usize] = x;
+            let c = delta - k;
+            if delta % 2 != 0 && (-(d - 1)..=d - 1).contains(&c) && x + backward[(c + offset) as usize] >= n {
+                return ((x_start as usize, y_start as usize), (x as usize, (x - k) as usize));
+            }
+        }
+
+        for k in (-d..=d).step_by(2) {
+            let mut x = furthest(&backward, d, k);
+            let (x_start, y_start) = (x, x - k);
+            while x < n && x - k < m && a[(n - 1 - x) as usize] == b[(m - 1 - (x - k)) as usize] {
+                x += 1;
+            }
+            backward[(k + offset) as usize] = x;
+            let c = delta - k;
+            if delta % 2 == 0 && (-d..=d).contains(&c) && x + forward[(c + offset) as usize] >= n {
+                return (((n - x) as usize, (m - (x - k)) as usize), ((n - x_start) as usize, (m - y_start) as usize));
+            }
+        }
+    }
+    unreachable!("the forward and backward paths overlap after at most (n + m) / 2 rounds")
+}
+
+
+/// Returns the heading of each line: the last item (see [`item_starts`]) starting at or before the line.
+fn headings(lines: &[&str]) -> Vec<String> {
+    let mut heading = String::new();
+    item_starts(lines).into_iter().map(|item| {
+        if let Some((kind, name)) = item {
+            heading = format!("{kind} {name}");
+        }
+        heading.clone()
+    }).collect()
+}
+
+
+/// Returns a unified diff of the `old` and `new` text, labeled with `old_label` and `new_label`.
+/// Hunk headers name the item containing the change (e.g., `@@ -3,7 +3,8 @@ function mj_step`).
+/// With `color`, the diff is colored with ANSI escape codes. Identical texts result in an empty diff.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str, color: bool) -> String {
+    let (old_lines, new_lines): (Vec<_>, Vec<_>) = (old.lines().collect(), new.lines().collect());
+    let script = edits(&old_lines, &new_lines);
+    let changes: Vec<_> = script.iter().enumerate().filter(|(_, e)| !matches!(e, Edit::Equal(..))).map(|(i, _)| i).collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+    let paint = |code: &str, text: String| if color { format!("\x1b[{code}m{text}\x1b[0m") } else { text };
+    let new_headings = headings(&new_lines);
+
+    // Changes closer than twice the context are in the same hunk
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for &change in &changes {
+        let (start, end) = (change.saturating_sub(CONTEXT_LINES), (change + CONTEXT_LINES + 1).min(script.len()));
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = format!("{}\n{}\n", paint("1", format!("--- {old_label}")), paint("1", format!("+++ {new_label}")));
+    for (start, end) in hunks {
+        let hunk = &script[start..end];
+        // The lines of the hunk start after the lines before it
+        let (mut old_start, mut new_start) = (0, 0);
+        for edit in &script[..start] {
+            match edit {
+                Edit::Equal(..) => (old_start, new_start) = (old_start + 1, new_start + 1),
+                Edit::Delete(_) => old_start += 1,
+                Edit::Insert(_) => new_start += 1,
+            }
+        }
+        let old_count = hunk.iter().filter(|e| !matches!(e, Edit::Insert(_))).count();
+        let new_count = hunk.iter().filter(|e| !matches!(e, Edit::Delete(_))).count();
+        let heading = new_headings.get(new_start.min(new_lines.len().saturating_sub(1))).filter(|h| !h.is_empty());
+        let range = |start: usize, count: usize| if count == 0 { format!("{start},0") } else { format!("{},{count}", start + 1) };
+        let header = format!("@@ -{} +{} @@", range(old_start, old_count), range(new_start, new_count));
+        out += &paint("36", header);
+        if let Some(heading) = heading {
+            out += &format!(" {heading}");
+        }
+        out.push('\n');
+        for edit in hunk {
+            match *edit {
+                Edit::Equal(i, _) => out += &format!(" {}\n", old_lines[i]),
+                Edit::Delete(i) => out += &format!("{}\n", paint("31", format!("-{}", old_lines[i]))),
+                Edit::Insert(j) => out += &format!("{}\n", paint("32", format!("+{}", new_lines[j]))),
+            }
+        }
+    }
+    out
+}
+
+
+/// Returns the kind and name of the item starting at the `line`, if any.
+/// Functions in an impl block are named by the implementing type (`scope`), e.g., `MjModel::body_id`.
+fn item_of(line: &str, scope: &str) -> Option<(&'static str, String)> {
+    if let Some(capture) = FUNCTION_REGEX.captures(line) {
+        let name = &capture[1];
+        Some(("function", if scope.is_empty() || !line.starts_with(char::is_whitespace) { name.to_string() } else { format!("{scope}::{name}") }))
+    }
+    else if let Some(capture) = TYPE_REGEX.captures(line) {
+        Some(("type", capture[1].to_string()))
+    }
+    else if let Some(capture) = MACRO_CALL_REGEX.captures(line) {
+        Some(("macro call", format!("{} {} {}", &capture[1], &capture[2], &capture[3])))
+    }
+    else {
+        FIELD_REGEX.captures(line).map(|c| ("field", c[1].to_string()))
+    }
+}
+
+
+/// Returns the item (see [`item_of`]) starting at each line, if any.
+/// Lines of a function (its parameters and body) don't start items, e.g., `x: 1,` of a struct literal.
+fn item_starts(lines: &[&str]) -> Vec<Option<(&'static str, String)>> {
+    let mut scope = String::new();
+    let mut depth = 0;
+    // The brace depth of the current function and whether its body was entered
+    let mut function: Option<(isize, bool)> = None;
+    lines.iter().map(|line| {
+        let item = if function.is_some() { None } else { item_of(line, &scope) };
+        if let Some(capture) = IMPL_REGEX.captures(line) {
+            scope = capture[1].to_string();
+        }
+        else if line.starts_with('}') {
+            scope.clear();
+        }
+        if matches!(item, Some(("function", _))) {
+            function = Some((depth, false));
+        }
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if let Some((start, entered)) = &mut function {
+            *entered |= depth > *start;
+            // A function ends when its body is closed, or at the `;` of a declaration without a body
+            if *entered && depth <= *start || !*entered && line.trim_end().ends_with(';') {
+                function = None;
+            }
+        }
+        item
+    }).collect()
+}
+
+
+/// Splits the text into items (see [`item_of`]), returning their kind, name and lines (up to the next item).
+/// Doc comments and attributes belong to the following item. Lines before the first item and lines less indented than the item (e.g., the `}` closing the block of
+/// the last field) are not part of any item.
+fn items(text: &str) -> Vec<(&'static str, String, Vec<&str>)> {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let lines: Vec<_> = text.lines().collect();
+    let mut items: Vec<(&'static str, String, Vec<&str>)> = vec![];
+    let mut open = false;
+    for (&line, item) in lines.iter().zip(item_starts(&lines)) {
+        match item {
+            Some((kind, name)) => {
+                // Doc comments and attributes right before the item are part of it
+                let mut item_lines = vec![line];
+                if let Some((.., lines)) = items.last_mut() && open {
+                    while lines.len() > 1 && lines.last().is_some_and(|l| l.trim_start().starts_with("///") || l.trim_start().starts_with("#[")) {
+                        item_lines.insert(0, lines.pop().unwrap());
+                    }
+                }
+                items.push((kind, name, item_lines));
+                open = true;
+            }
+            None => if let Some((.., lines)) = items.last_mut() && open {
+                if line.trim().is_empty() || indent(line) >= indent(lines[0]) {
+                    lines.push(line);
+                } else {
+                    open = false;
+                }
+            },
+        }
+    }
+    // Trailing blank lines separate the items and are not their content
+    for (.., lines) in &mut items {
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+    }
+    items
+}
+
+
+/// Kind of change of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    New,
+    Removed,
+    Changed,
+}
+
+impl Change {
+    fn symbol(self) -> &'static str {
+        match self {
+            Change::New => "+",
+            Change::Removed => "-",
+            Change::Changed => "~",
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::New => write!(f, "new"),
+            Change::Removed => write!(f, "removed"),
+            Change::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+
+/// The changed items of a region (or file), e.g., 3 new fields and 1 removed function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    /// The label of the region (e.g., `src/wrappers/mj_data.rs [array slices]`).
+    pub label: String,
+    /// The changes in the order of the items: (change, kind, name).
+    pub changes: Vec<(Change, &'static str, String)>,
+    /// Whether the text changed at all, e.g., outside of any item.
+    pub text_changed: bool,
+    /// Color the symbols of the changes with ANSI escape codes.
+    pub color: bool,
+}
+
+impl Summary {
+    /// Compares the items of the `old` and `new` text.
+    pub fn new(label: impl Into<String>, old: &str, new: &str, color: bool) -> Self {
+        let (old_items, new_items) = (items(old), items(new));
+        let find = |items: &[(&'static str, String, Vec<&str>)], kind: &str, name: &str| {
+            items.iter().position(|(k, n, _)| *k == kind && n == name)
+        };
+        let mut changes = vec![];
+        for (kind, name, lines) in &new_items {
+            match find(&old_items, kind, name) {
+                None => changes.push((Change::New, *kind, name.clone())),
+                Some(i) if old_items[i].2 != *lines => changes.push((Change::Changed, *kind, name.clone())),
+                Some(_) => {}
+            }
+        }
+        for (kind, name, _) in &old_items {
+            if find(&new_items, kind, name).is_none() {
+                changes.push((Change::Removed, *kind, name.clone()));
+            }
+        }
+        Summary { label: label.into(), changes, text_changed: old != new, color }
+    }
+}
+
+impl Display for Summary {
+    /// E.g., `mj_data.rs [array slices]: 2 new fields, 1 removed function`, followed by a line per changed item.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Counts per change and kind, in the order of first appearance
+        let mut counts: Vec<(Change, &str, usize)> = vec![];
+        for change in [Change::New, Change::Removed, Change::Changed] {
+            for (_, kind, _) in self.changes.iter().filter(|(c, ..)| *c == change) {
+                match counts.iter_mut().find(|(c, k, _)| *c == change && k == kind) {
+                    Some((.., count)) => *count += 1,
+                    None => counts.push((change, kind, 1)),
+                }
+            }
+        }
+        let counts: Vec<_> = counts.iter()
+            .map(|(change, kind, count)| format!("{count} {change} {kind}{}", if *count == 1 { "" } else { "s" }))
+            .collect();
+        match (counts.is_empty(), self.text_changed) {
+            (false, _) => write!(f, "{}: {}", self.label, counts.join(", "))?,
+            (true, true) => write!(f, "{}: changes outside of items", self.label)?,
+            (true, false) => write!(f, "{}: no changes", self.label)?,
+        }
+        for (change, kind, name) in &self.changes {
+            let symbol = match (self.color, change) {
+                (false, _) => change.symbol().to_string(),
+                (true, Change::New) => format!("\x1b[32m{}\x1b[0m", change.symbol()),
+                (true, Change::Removed) => format!("\x1b[31m{}\x1b[0m", change.symbol()),
+                (true, Change::Changed) => format!("\x1b[33m{}\x1b[0m", change.symbol()),
+            };
+            write!(f, "\n  {symbol} {kind} {name}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! Module for locating MuJoCo's headers and documentation in a release or source tree.
+//! Files are located by name, e.g., mujoco.h in include/mujoco and APItypes.rst in doc/APIreference.
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+
+/// Files, which could not be found in the MuJoCo tree.
+#[derive(Debug)]
+pub struct NotFound {
+    pub root: PathBuf,
+    /// The names of the missing files, along with the searched locations (relative to the root).
+    pub missing: Vec<(String, Vec<PathBuf>)>,
+}
+
+impl Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not find in the MuJoCo tree {}:", self.root.display())?;
+        for (name, searched) in &self.missing {
+            let searched = searched.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+            write!(f, "\n  {name} (searched {})", searched.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+
+/// A MuJoCo release or source tree.
+#[derive(Debug, Clone)]
+pub struct MujocoRoot {
+    pub root: PathBuf,
+}
+
+impl MujocoRoot {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the locations (relative to the root), where the file `name` is searched.
+    /// - indexer_xmacro.h: python/mujoco
+    /// - references.h (the documentation struct header): doc/includes
+    /// - other headers: include/mujoco
+    /// - reStructuredText documentation: doc/APIreference and doc
+    /// - introspect metadata (e.g., functions.py): python/mujoco/introspect
+    pub fn locations(name: &str) -> Vec<PathBuf> {
+        let directories: &[&str] = match name {
+            "indexer_xmacro.h" => &["python/mujoco"],
+            "references.h" => &["doc/includes"],
+            _ if name.ends_with(".h") => &["include/mujoco"],
+            _ if name.ends_with(".rst") => &["doc/APIreference", "doc"],
+            _ if name.ends_with(".py") => &["python/mujoco/introspect"],
+            _ => &[""],
+        };
+        directories.iter().map(|directory| Path::new(directory).join(name)).collect()
+    }
+
+    /// Locates the files named `names`, failing with all the files that could not be found.
+    pub fn locate_all<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Result<Vec<PathBuf>, NotFound> {
+        let mut found = vec![];
+        let mut missing = vec![];
+        for name in names {
+            let locations = Self::locations(name);
+            match locations.iter().map(|location| self.root.join(location)).find(|path| path.is_file()) {
+                Some(path) => found.push(path),
+                None => missing.push((name.to_string(), locations)),
+            }
+        }
+        if missing.is_empty() { Ok(found) } else { Err(NotFound { root: self.root.clone(), missing }) }
+    }
+
+    /// Returns all the headers of include/mujoco, sorted by name.
+    pub fn headers(&self) -> Result<Vec<PathBuf>, NotFound> {
+        let include_dir = self.root.join("include/mujoco");
+        let not_found = || NotFound { root: self.root.clone(), missing: vec![("*.h".to_string(), vec!["include/mujoco".into()])] };
+        let mut headers: Vec<_> = fs::read_dir(&include_dir).map_err(|_| not_found())?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|e| e == "h"))
+            .collect();
+        if headers.is_empty() {
+            return Err(not_found());
+        }
+        headers.sort();
+        Ok(headers)
+    }
+}
@@ -1,112 +1,431 @@
 //! Module for the CreateFixedArrayFunctionWrappers command.
 
-use std::collections::VecDeque;
-use std::path::Path;
-use std::fs;
+use std::io::Write;
 use inflector::Inflector;
-use regex;
-
-
-const RE_FUNCTION_DECL: &str = r"(?s)((?://[^\r\n]*\r?\n)+)\s*MJAPI\s+((?:const)?\s*(?:[A-z0-9_*]+))\s+(\w+)\s*\((.+?)\)";
-
-
-pub fn create_fixed_array_fn_wrappers(mujoco_h_path: &Path) {
-    let filedata = fs::read_to_string(mujoco_h_path).expect("could not read the file");
-        /* Parse declaration */
-    let re = regex::Regex::new(RE_FUNCTION_DECL).unwrap();
-    let mut parameter_parts: VecDeque<_>;
-    let mut mutability;
-    let mut position_start;
-    let mut position_end;
-    let mut parameter_end;
-    let mut param_type;
-    let mut parameter_arr_len;
-    let mut parameters_joined;
-    let mut return_type_out;
-    let mut out_parameters = Vec::new();
-    let mut out_parameters_names = Vec::new();
-    let mut parameter_name;
-    let mut comment_string;
-    'fn_loop: for capture in re.captures_iter(&filedata) {
-        let (comment, return_type, fn_name, param_string) = (
-            capture.get(1).unwrap().as_str(),
-            capture.get(2).unwrap().as_str(),
-            capture.get(3).unwrap().as_str(),
-            capture.get(4).unwrap().as_str()
-        );
-
-        if return_type.ends_with("*") || param_string.contains("*") {  // we don't want pointers
-            continue;
+
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::{Function, Ir, Param};
+use crate::layout_check::rust_ident;
+use crate::smoke_test::{self, SmokeTest};
+
+
+/// Converts the documentation of a function into a Rust doc comment.
+pub(crate) fn doc_comment(function: &Function) -> String {
+    function.doc.iter().map(|x|
+        format!("///{x}").replace("[", r"\[")
+            .replace("]", r"\]")
+        ).collect::<Vec<_>>().join("\n")
+}
+
+
+/// The quaternion parameters (function, parameter), which are not rotations, e.g., the derivative of mju_derivQuat.
+/// The others are rotations, thus unit quaternions.
+const NON_ROTATION_QUATERNIONS: [(&str, &str); 1] = [("mju_derivQuat", "res")];
+
+
+/// A linear algebra library, whose types replace the fixed-size arrays in an additional layer of wrappers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Linalg {
+    /// `Vector3`, `UnitQuaternion` (`Quaternion` for non-rotations), `Matrix3` and `Isometry3`
+    /// (of a position and quaternion parameter).
+    Nalgebra,
+    /// `DVec3`, `DQuat` and `DMat3`.
+    Glam,
+}
+
+
+/// The kind of a fixed-size mjtNum array, converted into a type of a [`Linalg`] library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ArrayKind {
+    /// 3 elements.
+    Vector,
+    /// 4 elements, ordered as [w, x, y, z], which are not a rotation (see [`NON_ROTATION_QUATERNIONS`]),
+    /// thus not normalized.
+    Quaternion,
+    /// 4 elements of a unit quaternion, ordered as [w, x, y, z].
+    Rotation,
+    /// 9 elements of a row-major 3x3 matrix.
+    Matrix,
+    /// A position (pos...) followed by a quaternion (quat...) with the same suffix, e.g., pos1 and quat1.
+    Pose,
+}
+
+impl ArrayKind {
+    fn name(self) -> &'static str {
+        match self {
+            ArrayKind::Vector => "vector",
+            ArrayKind::Quaternion => "quaternion",
+            ArrayKind::Rotation => "rotation",
+            ArrayKind::Matrix => "matrix",
+            ArrayKind::Pose => "pose",
+        }
+    }
+}
+
+impl Linalg {
+    /// Name of the library, which is also the name of the output feature enabling the layer.
+    pub fn name(self) -> &'static str {
+        match self {
+            Linalg::Nalgebra => "nalgebra",
+            Linalg::Glam => "glam",
         }
+    }
 
-        out_parameters.clear();
-        out_parameters_names.clear();
-        for parameter in param_string.split(",") {
-            parameter_parts = parameter.split_ascii_whitespace().collect();
+    fn rust_type(self, kind: ArrayKind) -> &'static str {
+        match (self, kind) {
+            (Linalg::Nalgebra, ArrayKind::Vector) => "Vector3<MjtNum>",
+            (Linalg::Nalgebra, ArrayKind::Quaternion) => "Quaternion<MjtNum>",
+            (Linalg::Nalgebra, ArrayKind::Rotation) => "UnitQuaternion<MjtNum>",
+            (Linalg::Nalgebra, ArrayKind::Matrix) => "Matrix3<MjtNum>",
+            (Linalg::Nalgebra, ArrayKind::Pose) => "Isometry3<MjtNum>",
+            (Linalg::Glam, ArrayKind::Vector) => "DVec3",
+            (Linalg::Glam, ArrayKind::Quaternion | ArrayKind::Rotation) => "DQuat",
+            (Linalg::Glam, ArrayKind::Matrix) => "DMat3",
+            (Linalg::Glam, ArrayKind::Pose) => unreachable!("glam has no pose type"),
+        }
+    }
 
-            if parameter_parts.len() < 2 {  // Ignore void declarations or declarations without a type.
-                continue 'fn_loop;
+    /// Returns the kind of the array parameter of the function, if it can be converted.
+    fn array_kind(self, function: &Function, param: &Param) -> Option<ArrayKind> {
+        match (param.c_type.as_str(), param.array_len.as_deref()?) {
+            ("mjtNum", "3") => Some(ArrayKind::Vector),
+            ("mjtNum", "4") if NON_ROTATION_QUATERNIONS.contains(&(function.name.as_str(), param.name.as_str())) => {
+                Some(ArrayKind::Quaternion)
             }
+            ("mjtNum", "4") => Some(ArrayKind::Rotation),
+            ("mjtNum", "9") => Some(ArrayKind::Matrix),
+            _ => None,
+        }
+    }
 
-            parameter_end = parameter_parts[parameter_parts.len() -1];
-            if parameter_end.ends_with("]") {  // is an array, thus a pointer from rust's level
-                /* Find the length part of the array */
-                position_start = parameter_end.chars().position(|c| c == '[').unwrap();
-                position_end = parameter_end.len() - 1;
-                parameter_arr_len = &parameter_end[position_start + 1 .. position_end];
+    /// Returns the functions converting between the type of the `kind` and MuJoCo's array(s).
+    fn conversions(self, kind: ArrayKind) -> &'static str {
+        match (self, kind) {
+            (Linalg::Nalgebra, ArrayKind::Vector) => "\
+fn vector_to_mj(vector: &Vector3<MjtNum>) -> [MjtNum; 3] {
+    [vector.x, vector.y, vector.z]
+}
 
-                /* Parameter name */
-                parameter_name = parameter_end[..position_start].to_snake_case();
+fn vector_from_mj(array: &[MjtNum; 3]) -> Vector3<MjtNum> {
+    Vector3::new(array[0], array[1], array[2])
+}",
+            (Linalg::Nalgebra, ArrayKind::Quaternion) => "\
+/// MuJoCo's quaternions are ordered as [w, x, y, z].
+fn quaternion_to_mj(quaternion: &Quaternion<MjtNum>) -> [MjtNum; 4] {
+    [quaternion.w, quaternion.i, quaternion.j, quaternion.k]
+}
 
-                /* Obtain the reference operator and make the parameter call either .as_ptr() or .as_mut_ptr() */
-                (mutability, param_type) = if parameter_parts[0] == "const" {
-                    out_parameters_names.push(format!("{parameter_name}.as_ptr()"));
-                    ("&", parameter_parts[1])  // index 1 has the type
-                }
-                else {
-                    out_parameters_names.push(format!("{parameter_name}.as_mut_ptr()"));
-                    ("&mut ", parameter_parts[0])  // index 0 has the type
-                };
+fn quaternion_from_mj(array: &[MjtNum; 4]) -> Quaternion<MjtNum> {
+    Quaternion::new(array[0], array[1], array[2], array[3])
+}",
+            (Linalg::Nalgebra, ArrayKind::Rotation) => "\
+/// MuJoCo's quaternions are ordered as [w, x, y, z].
+fn rotation_to_mj(rotation: &UnitQuaternion<MjtNum>) -> [MjtNum; 4] {
+    [rotation.w, rotation.i, rotation.j, rotation.k]
+}
 
-                if param_type.starts_with("mj") {
-                    out_parameters.push(format!("{}: {mutability}[{}; {parameter_arr_len}]", parameter_name, param_type.to_pascal_case()));
-                }
-                else {
-                    out_parameters.push(format!("{}: {mutability}[std::ffi::c_{}; {parameter_arr_len}]", parameter_name, param_type));
+/// The rotations computed by MuJoCo are unit quaternions up to rounding errors, which are normalized.
+fn rotation_from_mj(array: &[MjtNum; 4]) -> UnitQuaternion<MjtNum> {
+    UnitQuaternion::from_quaternion(Quaternion::new(array[0], array[1], array[2], array[3]))
+}",
+            (Linalg::Nalgebra, ArrayKind::Matrix) => "\
+/// MuJoCo's matrices are row-major, nalgebra's are column-major.
+fn matrix_to_mj(matrix: &Matrix3<MjtNum>) -> [MjtNum; 9] {
+    std::array::from_fn(|i| matrix[(i / 3, i % 3)])
+}
+
+fn matrix_from_mj(array: &[MjtNum; 9]) -> Matrix3<MjtNum> {
+    Matrix3::from_row_slice(array)
+}",
+            (Linalg::Nalgebra, ArrayKind::Pose) => "\
+fn pose_to_mj(pose: &Isometry3<MjtNum>) -> ([MjtNum; 3], [MjtNum; 4]) {
+    (vector_to_mj(&pose.translation.vector), rotation_to_mj(&pose.rotation))
+}
+
+fn pose_from_mj(pos: &[MjtNum; 3], quat: &[MjtNum; 4]) -> Isometry3<MjtNum> {
+    Isometry3::from_parts(vector_from_mj(pos).into(), rotation_from_mj(quat))
+}",
+            (Linalg::Glam, ArrayKind::Vector) => "\
+fn vector_to_mj(vector: &DVec3) -> [MjtNum; 3] {
+    vector.to_array()
+}
+
+fn vector_from_mj(array: &[MjtNum; 3]) -> DVec3 {
+    DVec3::from_array(*array)
+}",
+            (Linalg::Glam, ArrayKind::Quaternion) => "\
+/// MuJoCo's quaternions are ordered as [w, x, y, z], glam's as [x, y, z, w].
+/// `DQuat::from_xyzw` doesn't normalize, thus non-unit quaternions are kept as they are.
+fn quaternion_to_mj(quaternion: &DQuat) -> [MjtNum; 4] {
+    [quaternion.w, quaternion.x, quaternion.y, quaternion.z]
+}
+
+fn quaternion_from_mj(array: &[MjtNum; 4]) -> DQuat {
+    DQuat::from_xyzw(array[1], array[2], array[3], array[0])
+}",
+            (Linalg::Glam, ArrayKind::Rotation) => "\
+/// MuJoCo's quaternions are ordered as [w, x, y, z], glam's as [x, y, z, w].
+fn rotation_to_mj(rotation: &DQuat) -> [MjtNum; 4] {
+    [rotation.w, rotation.x, rotation.y, rotation.z]
+}
+
+/// The rotations computed by MuJoCo are unit quaternions up to rounding errors, which are normalized.
+fn rotation_from_mj(array: &[MjtNum; 4]) -> DQuat {
+    DQuat::from_xyzw(array[1], array[2], array[3], array[0]).normalize()
+}",
+            (Linalg::Glam, ArrayKind::Matrix) => "\
+/// MuJoCo's matrices are row-major, glam's are column-major.
+fn matrix_to_mj(matrix: &DMat3) -> [MjtNum; 9] {
+    matrix.transpose().to_cols_array()
+}
+
+fn matrix_from_mj(array: &[MjtNum; 9]) -> DMat3 {
+    DMat3::from_cols_array(array).transpose()
+}",
+            (Linalg::Glam, ArrayKind::Pose) => unreachable!("glam has no pose type"),
+        }
+    }
+
+    /// Returns the imports of the types used by the `kinds`.
+    fn imports(self, kinds: &[ArrayKind]) -> String {
+        match self {
+            Linalg::Nalgebra => {
+                let mut types: Vec<&str> = vec![];
+                for kind in kinds {
+                    types.extend(match kind {
+                        ArrayKind::Vector => &["Vector3"][..],
+                        ArrayKind::Quaternion => &["Quaternion"][..],
+                        ArrayKind::Rotation => &["Quaternion", "UnitQuaternion"],
+                        ArrayKind::Matrix => &["Matrix3"],
+                        ArrayKind::Pose => &["Isometry3"],
+                    });
                 }
+                types.sort();
+                types.dedup();
+                format!("use nalgebra::{{{}}};", types.join(", "))
             }
-            else {
-                param_type = parameter_parts[0];
-                if param_type.starts_with("mj") {
-                    out_parameters.push(format!("{}: {}", parameter_parts[1].to_snake_case(), param_type.to_pascal_case()));
-                }
-                else {
-                    out_parameters.push(format!("{}: std::ffi::c_{}", parameter_parts[1].to_snake_case(), param_type));
-                }
-                out_parameters_names.push(parameter_parts[1].to_snake_case());
+            Linalg::Glam => {
+                let mut types: Vec<_> = kinds.iter().map(|&kind| self.rust_type(kind)).collect();
+                types.sort();
+                types.dedup();
+                format!("use glam::{{{}}};", types.join(", "))
             }
         }
+    }
+}
+
+
+/// Creates the wrapper of the (already wrapped) function, which uses the types of the `linalg` library
+/// instead of the fixed-size arrays, and adds the used kinds of arrays to `kinds`.
+/// Returns [`None`] if an array can't be converted or there are no arrays.
+fn linalg_wrapper(function: &Function, linalg: Linalg, kinds: &mut Vec<ArrayKind>) -> Option<String> {
+    let (mut parameters, mut arguments, mut before, mut after, mut used) = (vec![], vec![], vec![], vec![], vec![]);
+    let mut params = function.params.iter().peekable();
+    while let Some(param) = params.next() {
+        let name = param.name.to_snake_case();
+        if param.array_len.is_none() {
+            let c_type = &param.c_type;
+            parameters.push(format!("{name}: {}", if c_type.starts_with("mj") { c_type.to_pascal_case() } else { format!("std::ffi::c_{c_type}") }));
+            arguments.push(name);
+            continue;
+        }
+        let kind = linalg.array_kind(function, param)?;
+        let (reference, mutability) = if param.is_const { ("&", "") } else { ("&mut ", "mut ") };
 
-        parameters_joined = out_parameters.join(", ");
-        if return_type == "void" {
-            return_type_out = String::new();
+        // A position followed by a quaternion with the same suffix is a pose
+        let suffix = param.name.strip_prefix("pos");
+        let quat = params.peek().filter(|q| {
+            suffix.is_some_and(|suffix| q.name.strip_prefix("quat") == Some(suffix) && q.is_const == param.is_const)
+                && q.array_len.as_deref() == Some("4") && kind == ArrayKind::Vector
+        });
+        if let (Linalg::Nalgebra, Some(quat)) = (linalg, quat) {
+            let suffix = suffix.unwrap();
+            let pose = if suffix.is_empty() { "pose".to_string() } else { format!("pose_{suffix}").to_snake_case() };
+            let (pos, quat) = (format!("{name}_mj"), format!("{}_mj", quat.name.to_snake_case()));
+            parameters.push(format!("{pose}: {reference}{}", linalg.rust_type(ArrayKind::Pose)));
+            before.push(format!("let ({mutability}{pos}, {mutability}{quat}) = pose_to_mj({pose});"));
+            arguments.extend([format!("{reference}{pos}"), format!("{reference}{quat}")]);
+            if !param.is_const {
+                after.push(format!("*{pose} = pose_from_mj(&{pos}, &{quat});"));
+            }
+            used.extend([ArrayKind::Vector, ArrayKind::Rotation, ArrayKind::Pose]);
+            params.next();
+            continue;
         }
-        else if return_type.starts_with("mj") {
-            return_type_out = format!(" -> {}", return_type.to_pascal_case());
+
+        let kind_name = kind.name();
+        parameters.push(format!("{name}: {reference}{}", linalg.rust_type(kind)));
+        if param.is_const {
+            arguments.push(format!("&{kind_name}_to_mj({name})"));
         }
         else {
-            return_type_out = format!(" -> std::ffi::c_{return_type}");
-        };
+            before.push(format!("let mut {name}_mj = {kind_name}_to_mj({name});"));
+            arguments.push(format!("&mut {name}_mj"));
+            after.push(format!("*{name} = {kind_name}_from_mj(&{name}_mj);"));
+        }
+        used.push(kind);
+    }
+    if used.is_empty() {
+        return None;
+    }
+    for kind in used {
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+
+    let return_type = &function.return_type;
+    let return_type_out = match return_type.as_str() {
+        "void" => String::new(),
+        _ if return_type.starts_with("mj") => format!(" -> {}", return_type.to_pascal_case()),
+        _ => format!(" -> std::ffi::c_{return_type}"),
+    };
+    let call = format!("super::{}({})", function.name.to_snake_case(), arguments.join(", "));
+    let body = match (after.is_empty(), return_type_out.is_empty()) {
+        (true, _) => before.into_iter().chain([call]).collect::<Vec<_>>(),
+        (false, true) => before.into_iter().chain([format!("{call};")]).chain(after).collect(),
+        (false, false) => before.into_iter().chain([format!("let result = {call};")]).chain(after).chain(["result".into()]).collect(),
+    };
+    Some(format!("{}
+pub fn {}({}){return_type_out} {{
+    {}
+}}", doc_comment(function), function.name.to_snake_case(), parameters.join(", "), body.join("\n    ")))
+}
+
+
+/// Writes the module of the `linalg` layer, enabled by the output feature of the same name.
+fn write_linalg_module(linalg: Linalg, kinds: &[ArrayKind], wrappers: &[String], out: &mut dyn Write) -> std::io::Result<()> {
+    let name = linalg.name();
+    let mut kinds = kinds.to_vec();
+    kinds.sort();
+    let conversions: Vec<_> = kinds.iter().map(|&kind| linalg.conversions(kind)).collect();
+    let content = format!("//! The wrappers using the types of {name} instead of the fixed-size arrays.\n{}\nuse super::*;\n\n{}\n\n{}",
+        linalg.imports(&kinds), conversions.join("\n\n"), wrappers.join("\n\n"));
+    let content = content.lines().map(|line| if line.is_empty() { String::new() } else { format!("    {line}") }).collect::<Vec<_>>().join("\n");
+    writeln!(out, "
+#[cfg(feature = \"{name}\")]
+pub mod {name}_wrappers {{
+{content}
+}}")
+}
+
+
+/// Generates wrappers around functions that have fixed-sized arrays as parameters.
+#[derive(Debug, Clone, Default)]
+pub struct FixedArrayFnGenerator {
+    /// Also create a test module with a smoke test of each wrapper.
+    pub with_tests: bool,
+    /// Also create a module of wrappers using the types of each library (3, 4 and 9 element arrays
+    /// become vectors, quaternions and 3x3 matrices), enabled by the output feature named after the library.
+    pub linalg: Vec<Linalg>,
+}
+
+impl Generator for FixedArrayFnGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let mut mutability;
+        let mut out_parameters = Vec::new();
+        let mut out_parameters_names = Vec::new();
+        let mut parameter_name;
+        let mut test_arguments = Vec::new();
+        let mut smoke_tests = Vec::new();
+        let mut linalg_layers: Vec<_> = self.linalg.iter().map(|&linalg| (linalg, vec![], vec![])).collect();
+        for function in &ir.functions {
+            let Function { name: fn_name, return_type, params, .. } = function;
+            // we don't want pointers
+            if return_type.ends_with("*") {
+                report.skip("function", fn_name, "returns a pointer");
+                continue;
+            }
 
-        comment_string = comment.lines().map(|x|
-            x.replace("//", "///").replace("[", r"\[")
-                .replace("]", r"\]")
-            ).collect::<Vec<_>>().join("\n");
+            if let Some(param) = params.iter().find(|p| p.is_pointer()) {
+                report.skip("function", fn_name, format!("parameter {} is a pointer", param.name));
+                continue;
+            }
+
+            if params.is_empty() {  // Ignore void declarations.
+                report.skip("function", fn_name, "has no parameters");
+                continue;
+            }
+
+            out_parameters.clear();
+            out_parameters_names.clear();
+            test_arguments.clear();
+            for parameter in params {
+                let param_type = &parameter.c_type;
+                parameter_name = rust_ident(&parameter.name.to_snake_case());
+                if let Some(parameter_arr_len) = &parameter.array_len {  // is an array, thus a pointer from rust's level
+                    /* Obtain the reference operator and make the parameter call either .as_ptr() or .as_mut_ptr() */
+                    mutability = if parameter.is_const {
+                        out_parameters_names.push(format!("{parameter_name}.as_ptr()"));
+                        "&"
+                    }
+                    else {
+                        out_parameters_names.push(format!("{parameter_name}.as_mut_ptr()"));
+                        "&mut "
+                    };
+
+                    test_arguments.push(smoke_test::argument_value(param_type, Some(parameter_arr_len), mutability != "&"));
+                    if param_type.starts_with("mj") {
+                        out_parameters.push(format!("{}: {mutability}[{}; {parameter_arr_len}]", parameter_name, param_type.to_pascal_case()));
+                    }
+                    else {
+                        out_parameters.push(format!("{}: {mutability}[std::ffi::c_{}; {parameter_arr_len}]", parameter_name, param_type));
+                    }
+                }
+                else {
+                    test_arguments.push(smoke_test::argument_value(param_type, None, false));
+                    if param_type.starts_with("mj") {
+                        out_parameters.push(format!("{}: {}", parameter_name, param_type.to_pascal_case()));
+                    }
+                    else {
+                        out_parameters.push(format!("{}: std::ffi::c_{}", parameter_name, param_type));
+                    }
+                    out_parameters_names.push(parameter_name);
+                }
+            }
+
+            let parameters_joined = out_parameters.join(", ");
+            let return_type_out = if return_type == "void" {
+                String::new()
+            }
+            else if return_type.starts_with("mj") {
+                format!(" -> {}", return_type.to_pascal_case())
+            }
+            else {
+                format!(" -> std::ffi::c_{return_type}")
+            };
 
-        println!("
-{comment_string}
+            writeln!(out, "
+{}
 pub fn {}({parameters_joined}){return_type_out}  {{
     unsafe {{ mujoco_c::{fn_name}({}) }}
-}}", fn_name.to_snake_case(), out_parameters_names.join(", "));
+}}", doc_comment(function), fn_name.to_snake_case(), out_parameters_names.join(", "))?;
+
+            for (linalg, kinds, wrappers) in &mut linalg_layers {
+                wrappers.extend(linalg_wrapper(function, *linalg, kinds));
+            }
+
+            // Create a smoke test, if all the arguments can be trivially constructed
+            if let Some(arguments) = test_arguments.iter().cloned().collect::<Option<Vec<_>>>() {
+                let result_binding = if return_type_out.is_empty() { "" } else { "let _ = " };
+                let name = fn_name.to_snake_case();
+                smoke_tests.push(SmokeTest { body: format!("{result_binding}{name}({});", arguments.join(", ")), name });
+            }
+            else if self.with_tests && let Some((param, _)) = params.iter().zip(&test_arguments).find(|(_, a)| a.is_none()) {
+                report.skip("smoke test", fn_name.to_snake_case(), format!("parameter {} can't be trivially constructed", param.name));
+            }
+        }
+
+        for (linalg, kinds, wrappers) in &linalg_layers {
+            if !wrappers.is_empty() {
+                write_linalg_module(*linalg, kinds, wrappers, out)?;
+            }
+        }
+
+        if self.with_tests {
+            smoke_test::write_smoke_tests(&smoke_tests, out)?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,62 @@
+//! The common interface of all the code generators.
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+
+use crate::ir::Ir;
+use crate::report::Report;
+
+
+/// A code generator, which creates Rust code from the [IR](crate::ir).
+pub trait Generator {
+    /// Writes the generated code into `out` and records the declarations, for which no code was generated, into `report`.
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error>;
+
+    /// Writes the generated code into `out`.
+    fn generate(&self, ir: &Ir, out: &mut dyn Write) -> Result<(), Error> {
+        self.generate_with_report(ir, out, &mut Report::default())
+    }
+
+    /// Returns the generated code.
+    fn generate_string(&self, ir: &Ir) -> Result<String, Error> {
+        let mut out = vec![];
+        self.generate(ir, &mut out)?;
+        Ok(String::from_utf8(out).expect("generated code is not valid UTF-8"))
+    }
+}
+
+
+/// Errors that can occur during generation.
+#[derive(Debug)]
+pub enum Error {
+    /// Writing the output failed.
+    Io(io::Error),
+    /// The struct required by the generator is not in the IR.
+    MissingStruct(String),
+    /// The output template could not be rendered (e.g., it uses an unknown variable).
+    Template(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "failed to write the output: {error}"),
+            Error::MissingStruct(name) => write!(f, "failed to find struct {name}"),
+            Error::Template(message) => write!(f, "failed to render the output: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::MissingStruct(_) | Error::Template(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
@@ -1,12 +1,19 @@
-use std::cell::LazyCell;
+//! Module for creating getter/setter macro calls of non-array and fixed-sized array struct fields.
+use std::sync::LazyLock;
 use std::collections::HashMap;
-use std::path::Path;
-use std::fs;
+use std::io::Write;
 use inflector::Inflector;
-use regex::{self, Regex};
+use regex::Regex;
 
+use crate::constraints::{self, Constraint, FieldMeta};
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::{Field, Ir};
+use crate::layout_check::bindgen_name;
+use crate::template::{self, Context, Template};
 
-const C_TO_RUST_TYPE_MAPPING: LazyCell<HashMap<&'static str, &'static str>> = LazyCell::new(
+
+static C_TO_RUST_TYPE_MAPPING: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(
     || HashMap::from_iter([
         ("float", "f32"),
         ("double", "f64"),
@@ -17,14 +24,104 @@ const C_TO_RUST_TYPE_MAPPING: LazyCell<HashMap<&'static str, &'static str>> = La
     ])
 );
 
-const ENUM_REGEX: LazyCell<Regex> = LazyCell::new(|| Regex::new(r"(.*?)\((mjt[A-z]+)\)").unwrap());
-const ATTR_NORMAL_REGEX: LazyCell<Regex> = LazyCell::new(|| Regex::new(r"(?m)((?:unsigned\s+)?[^\s*]+)(?:\s+([^\s\[]+));\s+//\s*(.*)$").unwrap());
-const ATTR_ARRAY_REGEX: LazyCell<Regex> = LazyCell::new(|| Regex::new(r"(?m)((?:unsigned\s+)?[^\s*]+)(?:\s+([^\s]+)\[(.+)\]);\s+//\s*(.*)$").unwrap());
+static ENUM_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(.*?)\((mjt[A-z]+)\)").unwrap());
+
+
+/// The default template, writing the getter/setter entries of scalar and array fields.
+const DEFAULT_TEMPLATE: &str = include_str!("../templates/getters_setters.tpl");
+
+
+/// Error type returned by the validating setters. Needs to be defined once in the output crate.
+const VALUE_RANGE_ERROR_TYPE: &str = r#"/// Error returned by validating setters when the value is outside of the valid range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MjValueRangeError {
+    /// Name of the field that failed to be set.
+    pub field: &'static str,
+    /// The violated constraint (e.g., "> 0").
+    pub constraint: &'static str,
+}
+
+impl std::fmt::Display for MjValueRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value of '{}' must satisfy: {}", self.field, self.constraint)
+    }
+}
+
+impl std::error::Error for MjValueRangeError {}"#;
+
+const STRING_ERROR_TYPE: &str = r#"/// Error returned by string setters when the value can't be stored in the (NUL-terminated) char array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MjStringError {
+    /// The value doesn't fit into the char array.
+    TooLong {
+        /// Name of the field that failed to be set.
+        field: &'static str,
+        /// The maximum length of the string in bytes, excluding the terminating NUL.
+        capacity: usize,
+        /// The length of the rejected value in bytes.
+        length: usize,
+    },
+    /// The value contains a NUL byte, which would terminate the string early.
+    InteriorNul {
+        /// Name of the field that failed to be set.
+        field: &'static str,
+        /// The position of the first NUL byte in the value.
+        position: usize,
+    },
+}
+
+impl std::fmt::Display for MjStringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLong { field, capacity, length } => {
+                write!(f, "value of '{field}' is {length} bytes long, but at most {capacity} bytes fit")
+            }
+            Self::InteriorNul { field, position } => write!(f, "value of '{field}' contains a NUL byte at {position}"),
+        }
+    }
+}
+
+impl std::error::Error for MjStringError {}"#;
+
+
+/// A field collected for generating validating setters and default values.
+struct ParsedField<'a> {
+    name: &'a str,
+    /// Path of the field from the struct, through nested structs (e.g., global.fovy).
+    path: &'a str,
+    datatype: String,
+    /// Array size expression or [`None`] for scalars.
+    size: Option<String>,
+    comment: &'a str,
+    was_enum: bool,
+}
+
+
+/// Generates getter/setter macro entries of the fields of a struct, along with validating
+/// setters for fields with known ranges and a table of default values.
+///
+/// The entries are rendered by the `template` (by default templates/getters_setters.tpl) with the lists
+/// `scalars` and `arrays`, whose items have the variables `name`, `type` and `doc`, along with:
+/// - `scalars`: the flags `reference` (a struct, accessed by reference) and `enum` (the type is an enum from the comment),
+/// - `arrays`: `size` (e.g., 3 or mjNREF as usize).
+///
+/// The string accessors and validating setters follow the entries.
+pub struct GetterSetterGenerator {
+    /// The C name of the struct (e.g., mjOption_).
+    pub struct_name: String,
+    /// Ranges and default values of the fields, see [`constraints::collect_metadata`]. They are looked up by the path
+    /// of the field (e.g., global.fovy), otherwise by its name, if no other field of the struct has the same name.
+    pub metadata: HashMap<String, FieldMeta>,
+    /// Also create the error type returned by the validating setters.
+    pub error_type: bool,
+    /// The output template of the entries, replacing the default one.
+    pub template: Option<Template>,
+}
 
 
 /// Try to extract an enum type from the documentation string.
 /// E.g., dynamics type (mjtDyn) gets converted to ("dynamics type", "mjtDyn").
-fn extract_possible_enum<'a>(docstring: &'a str, datatype: &'a str) -> (&'a str, &'a str, bool) {
+pub(crate) fn extract_possible_enum<'a>(docstring: &'a str, datatype: &'a str) -> (&'a str, &'a str, bool) {
     if let Some(m) = ENUM_REGEX.captures(docstring) {
         let (_, [comment, enum_type]) = m.extract();
         (comment, enum_type, true)
@@ -36,7 +133,7 @@ fn extract_possible_enum<'a>(docstring: &'a str, datatype: &'a str) -> (&'a str,
 
 
 /// Postprocess a datatype to correct Rust type
-fn convert_type(datatype: &str) -> String {
+pub(crate) fn convert_type(datatype: &str) -> String {
     if datatype.starts_with("mj") {datatype.to_pascal_case()} else {
         if let Some(rust_type) = C_TO_RUST_TYPE_MAPPING.get(datatype) {
             rust_type.to_string()
@@ -45,42 +142,243 @@ fn convert_type(datatype: &str) -> String {
 }
 
 
-pub fn create_getters_setters(structs_filepath: &Path, struct_name: &str) {
-    let filedata = fs::read_to_string(structs_filepath).unwrap();
-    // Match the correct struct
-    let re = regex::Regex::new(&format!(r"(?s)struct\s+{struct_name}\s*\{{.*?\n}};")).unwrap();
-    let struct_data = re.find(&filedata).expect("failed to find struct or struct body").as_str();
-    let mut was_enum;
+impl Generator for GetterSetterGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let struct_ = ir.find_struct(&self.struct_name).ok_or_else(|| Error::MissingStruct(self.struct_name.clone()))?;
+        // Undocumented fields are considered private
+        let mut fields = vec![];
+        for (path, field) in struct_.flat_field_paths() {
+            let name = format!("{}.{path}", struct_.name);
+            if field.comment.is_empty() {
+                report.skip("field", name, "undocumented");
+            }
+            else if field.is_pointer() {
+                report.skip("field", name, "pointer");
+            }
+            else if field.array_dims.len() > 1 && !(field.c_type == "char" && field.array_dims.len() == 2) {
+                report.skip("field", name, "multi-dimensional array");
+            }
+            else if field.array_dims.iter().any(|dim| dim == "0") {
+                report.skip("field", name, "zero-length array");
+            }
+            else {
+                fields.push((path, field));
+            }
+        }
+        let mut parsed_fields = vec![];
+        let (mut scalars, mut arrays) = (vec![], vec![]);
+
+        for (path, field) in fields.iter().filter(|(_, f)| f.is_scalar()) {
+            let (comment, datatype, was_enum) = extract_possible_enum(&field.comment, &field.c_type);
+            let datatype_owned = convert_type(datatype);
+            let name = field.name.as_str();
+
+            // Complex types only allow references, others get a scalar getter/setter/builder
+            scalars.push(Context::new()
+                .text("name", name)
+                .text("type", datatype_owned.as_str())
+                .text("doc", comment.trim())
+                .flag("reference", datatype_owned.starts_with("Mj") && !datatype.starts_with("Mjt"))
+                .flag("enum", was_enum));
+            parsed_fields.push(ParsedField { name, path, datatype: datatype_owned, size: None, comment, was_enum });
+        }
+
+        for (path, field) in fields.iter().filter(|(_, f)| f.c_type != "char") {
+            let Some(size) = field.array_len() else { continue };
+            let (comment, datatype, _) = extract_possible_enum(&field.comment, &field.c_type);
+            let datatype_owned = convert_type(datatype);
+            let name = field.name.as_str();
+            let type_cast = if size.starts_with(char::is_alphabetic) { " as usize" } else {""};
+            arrays.push(Context::new()
+                .text("name", name)
+                .text("type", datatype_owned.as_str())
+                .text("size", format!("{size}{type_cast}"))
+                .text("doc", comment.trim()));
+            parsed_fields.push(ParsedField {
+                name, path, datatype: datatype_owned, size: Some(format!("{size}{type_cast}")), comment, was_enum: false
+            });
+        }
+
+        let context = Context::new().list("scalars", scalars).list("arrays", arrays);
+        write!(out, "{}", template::render_or_default(self.template.as_ref(), "templates/getters_setters.tpl", DEFAULT_TEMPLATE, &context)?)?;
+        write_strings(self, &fields, out)?;
+        write_validation(self, &parsed_fields, out, report)
+    }
+}
+
+
+/// Writes string getters and setters of the char array fields (e.g., `char title[1000]`), given with their paths.
+/// The getters read up to the first NUL and check the UTF-8 encoding. The setters copy the value
+/// and terminate it with NUL, rejecting values that don't fit or contain NUL.
+/// Two-dimensional char arrays (e.g., `char linename[mjMAXLINE][100]`) are arrays of strings, accessed by index,
+/// whose accessors panic when the index is out of bounds.
+fn write_strings(generator: &GetterSetterGenerator, fields: &[(String, &Field)], out: &mut dyn Write) -> Result<(), Error> {
+    let mut accessors = vec![];
+    for (path, field) in fields.iter().filter(|(_, f)| f.c_type == "char" && !f.is_pointer() && !f.array_dims.is_empty()) {
+        let (name, member, comment) = (path.replace('.', "_"), member_path(path), field.comment.trim());
+        let (index_param, index, capacity) = match &field.array_dims[..] {
+            [size] => ("", "", size),
+            [_, size] => (", index: usize", "[index]", size),
+            _ => continue,
+        };
+        let (index_doc, panics) = if index.is_empty() { ("", "") } else {
+            (" at `index`", "\n///\n/// # Panics\n/// When `index` is out of bounds.")
+        };
+        accessors.push(format!(
+"/// Returns `{path}`{index_doc}: {comment}.
+/// The string ends at the first NUL. Returns an error when it's not valid UTF-8.{panics}
+pub fn {name}(&self{index_param}) -> Result<&str, std::str::Utf8Error> {{
+    let chars = &self.ffi().{member}{index};
+    // SAFETY: c_char and u8 have the same size and alignment
+    let bytes = unsafe {{ std::slice::from_raw_parts(chars.as_ptr().cast::<u8>(), chars.len()) }};
+    let length = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    std::str::from_utf8(&bytes[..length])
+}}
 
-    println!("-----------------------------------------");
-    println!("Processing normal getters/setters/builders");
-    println!("-----------------------------------------");
-    for capture in ATTR_NORMAL_REGEX.captures_iter(struct_data) {
-        let (_, [mut datatype, name, mut comment]) = capture.extract();
-        (comment, datatype, was_enum) = extract_possible_enum(comment, datatype);
-        let datatype_owned = convert_type(datatype);
+/// Sets `{path}`{index_doc}: {comment}.
+/// Returns an error when the value doesn't fit into {capacity} bytes, including the terminating NUL, or contains NUL.{panics}
+pub fn set_{name}(&mut self{index_param}, value: &str) -> Result<(), MjStringError> {{
+    let chars = &mut self.ffi_mut().{member}{index};
+    if value.len() >= chars.len() {{
+        return Err(MjStringError::TooLong {{ field: \"{path}\", capacity: chars.len() - 1, length: value.len() }});
+    }}
+    if let Some(position) = value.bytes().position(|b| b == 0) {{
+        return Err(MjStringError::InteriorNul {{ field: \"{path}\", position }});
+    }}
+    for (c, b) in chars.iter_mut().zip(value.bytes()) {{
+        *c = b as std::ffi::c_char;
+    }}
+    chars[value.len()] = 0;
+    Ok(())
+}}"));
+    }
 
-        if datatype_owned.starts_with("Mj") && !datatype.starts_with("Mjt") {  // complex type, only allow references
-            print!("{name}: &{datatype_owned}; \"{}.\";", comment.trim())
+    if !accessors.is_empty() {
+        writeln!(out, "-----------------------------------------")?;
+        writeln!(out, "Processing string getters/setters")?;
+        writeln!(out, "-----------------------------------------")?;
+        if generator.error_type {
+            writeln!(out, "{STRING_ERROR_TYPE}\n")?;
         }
-        else {  // Create a scalar getter/setter/builder
-            print!("{name}: {datatype_owned}; \"{}.\";", comment.trim())
+        writeln!(out, "{}", accessors.join("\n\n"))?;
+    }
+    Ok(())
+}
+
+
+/// Converts the `path` of a field (e.g., global.type) to the path of bindgen's members (e.g., global.type_).
+fn member_path(path: &str) -> String {
+    path.split('.').map(bindgen_name).collect::<Vec<_>>().join(".")
+}
+
+
+/// Writes validating setters for fields with known constraints and a table of default values.
+/// Defaults with unsupported values are recorded into `report`.
+fn write_validation(
+    generator: &GetterSetterGenerator, parsed_fields: &[ParsedField], out: &mut dyn Write, report: &mut Report
+) -> Result<(), Error> {
+    let mut setters = vec![];
+    let mut defaults = vec![];
+    for field in parsed_fields {
+        let ParsedField { name, path, datatype, size, comment, was_enum } = field;
+        // Only numbers can be compared and have a numeric default
+        if *was_enum || datatype.starts_with("Mj") && datatype != "MjtNum" && datatype != "MjtByte" {
+            continue;
+        }
+
+        // The metadata of the path (e.g., map.haze), otherwise of the name, unless another field has the same name
+        let ambiguous = parsed_fields.iter().filter(|f| f.name == *name).count() > 1;
+        let skipped = format!("{}.{path}", generator.struct_name);
+        if ambiguous && generator.metadata.contains_key(*name) {
+            report.skip("metadata", &skipped, format!("`{name}` is ambiguous, the metadata must be given by the path"));
+        }
+        let meta = generator.metadata.get(*path).or_else(|| generator.metadata.get(*name).filter(|_| !ambiguous))
+            .cloned().unwrap_or_default();
+        let mut field_constraints = constraints::constraints_from_comment(comment);
+        for constraint in meta.constraints {  // other sources take precedence over comments
+            field_constraints.retain(|c| c.op != constraint.op);
+            field_constraints.push(constraint);
         }
 
-        if was_enum {
-            print!(" // force type coercion!")
+        let comment = comment.trim();
+        let (method, member) = (path.replace('.', "_"), member_path(path));
+        let mut conditions = vec![];
+        for Constraint { op, value } in &field_constraints {
+            match constraints::numeric_literal(value, datatype) {
+                Ok(literal) => conditions.push((format!("{op} {value}"), format!("{op} {literal}"))),
+                Err(reason) => report.skip("constraint", &skipped, format!("`{op} {value}`: {reason}")),
+            }
+        }
+
+        if !conditions.is_empty() {
+            let (value_type, element) = match size {
+                Some(size) => (format!("[{datatype}; {size}]"), "x"),
+                None => (datatype.clone(), "value"),
+            };
+            let checks = conditions.iter().map(|(constraint, condition)| {
+                let is_valid = if size.is_some() {
+                    format!("value.iter().all(|x| *x {condition})")
+                } else { format!("value {condition}") };
+                format!(
+"    if !({is_valid}) {{
+        return Err(MjValueRangeError {{ field: \"{path}\", constraint: \"{element} {constraint}\" }});
+    }}")
+            }).collect::<Vec<_>>().join("\n");
+
+            let constraints_doc = conditions.iter().map(|(c, _)| format!("{element} {c}")).collect::<Vec<_>>().join(", ");
+            setters.push(format!(
+"/// Sets `{path}`: {comment}.
+/// Returns an error when the value does not satisfy: {constraints_doc}.
+pub fn try_set_{method}(&mut self, value: {value_type}) -> Result<(), MjValueRangeError> {{
+{checks}
+    self.ffi_mut().{member} = value;
+    Ok(())
+}}"));
         }
-        println!();
+
+        if let Some(default) = &meta.default {
+            let literals: Result<Vec<_>, _> = default.split_whitespace()
+                .map(|v| constraints::numeric_literal(v, datatype)).collect();
+            match (literals, size) {
+                (Err(reason), _) => report.skip("default", skipped, reason),
+                (Ok(literals), None) if literals.len() == 1 => {
+                    defaults.push(format!("    /// Default `{path}`: {comment}.\n    pub const {}: {datatype} = {};", method.to_uppercase(), literals[0]));
+                }
+                (Ok(literals), Some(size)) => match size.parse::<usize>() {
+                    Ok(length) if length == literals.len() => {
+                        defaults.push(format!(
+                            "    /// Default `{path}`: {comment}.\n    pub const {}: [{datatype}; {size}] = [{}];", method.to_uppercase(), literals.join(", ")
+                        ));
+                    }
+                    Ok(length) => report.skip("default", skipped, format!("{} values for an array of length {length}", literals.len())),
+                    Err(_) => report.skip("default", skipped, format!("array length {size} is not a number")),
+                },
+                _ => report.skip("default", skipped, format!("unsupported value \"{default}\"")),
+            }
+        }
+    }
+
+    if !setters.is_empty() {
+        writeln!(out, "-----------------------------------------")?;
+        writeln!(out, "Processing validated setters")?;
+        writeln!(out, "-----------------------------------------")?;
+        if generator.error_type {
+            writeln!(out, "{VALUE_RANGE_ERROR_TYPE}\n")?;
+        }
+        writeln!(out, "{}", setters.join("\n\n"))?;
     }
 
-    println!("-----------------------------------------");
-    println!("Processing array getters/setters/builders");
-    println!("-----------------------------------------");
-    for capture in ATTR_ARRAY_REGEX.captures_iter(struct_data) {
-        let (_, [mut datatype, name, size, mut comment]) = capture.extract();
-        (comment, datatype, _) = extract_possible_enum(comment, datatype);
-        let datatype_owned = convert_type(datatype);
-        let type_cast = if size.chars().next().unwrap().is_alphabetic() { " as usize" } else {""};
-        println!("{name}: &[{datatype_owned}; {size}{type_cast}]; \"{}.\";", comment.trim());
+    if !defaults.is_empty() {
+        let type_name = generator.struct_name.trim_end_matches('_').to_pascal_case();
+        writeln!(out, "-----------------------------------------")?;
+        writeln!(out, "Processing default values")?;
+        writeln!(out, "-----------------------------------------")?;
+        writeln!(out, "/// Default values of [`{type_name}`] fields, as documented by MuJoCo.
+pub mod {}_default {{
+    use super::*;
+
+{}
+}}", type_name.to_snake_case(), defaults.join("\n\n"))?;
     }
+    Ok(())
 }
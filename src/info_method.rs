@@ -1,36 +1,63 @@
 //! Module for generating info method code to MjModel and MjData.
-use std::path::Path;
-use std::fs;
+use std::io::Write;
 
 use inflector::Inflector;
-use regex;
 
-const RE_DEFINE_PATTERN: &str = r"#define MJ(?<class>[A-z]+)_(?<item>[A-z]+).*?\)$";
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::{Ir, ViewEntry};
+use crate::object_id::referenced_id_type;
+use crate::template::{self, Context, Template};
+
 /// Allowed total array dimension names to consider for direct mapping. Names that are outside
 /// of this array will only be directly mapped if their length is greater than 2, as we
 /// assume those correspond to the item length and not the total array length.
 /// We assume that nx means length of an array, whose elements are not of fixed-length.
 const NX_ALLOWED_DIRECT_LENGTH: [&str; 1] = ["nu"];
 
-pub fn create_views(filepath: &Path) {
-    /* Read the contents of the file containing the view defines (indexer_xmacro.h) */
-    let filedata = fs::read_to_string(filepath).expect("could not read the file");
-
-    /* Create regex patterns */
-    // Match the entire define block
-    let re = regex::RegexBuilder::new(RE_DEFINE_PATTERN)
-        .dot_matches_new_line(true)
-        .multi_line(true)
-        .build().expect("invalid regex");
-
-    let mut info_method_calls = vec![];
-    let mut info_with_view_calls = vec![];
-    for capture in re.captures_iter(&filedata) {
-        let (class, item, capture_data) = (
-            capture.name("class").expect("could not find the class type").as_str().to_lowercase(),
-            capture.name("item").expect("could not find the item type").as_str(),
-            capture.get(0).unwrap().as_str()
-        );
+
+/// The default template, writing the info_method! and info_with_view! macro calls.
+const DEFAULT_TEMPLATE: &str = include_str!("../templates/views.tpl");
+
+
+/// Generates the info method and view macro calls for each view group (from indexer_xmacro.h).
+///
+/// The output is rendered by the `template` (by default templates/views.tpl) with the list `groups`,
+/// whose items have the variables:
+/// - `class` (e.g., Model), `item` (e.g., body) and the flag `data` (the class is Data),
+/// - `fixed_lengths`, `external_lengths` and `dynamic_lengths`: the `attribute: length` entries of info_method!,
+/// - `attributes`: the `[prefix] attribute: type [cast]` entries of info_with_view!,
+/// - `entries`: a list with the variables `name`, `prefix`, `type`, `length` and the flags `cast`,
+///   `fixed`, `external` and `dynamic` (the kind of length).
+///
+/// With `typed_ids`, the `int` entries referencing other objects have the type `BodyId`, etc.
+/// (see [`crate::object_id`]). The comments of the fields in `ir.structs` (e.g., mjModel_ of mjmodel.h)
+/// are used to infer the objects, which the names don't tell.
+#[derive(Default)]
+pub struct ViewsGenerator {
+    /// The output template, replacing the default one.
+    pub template: Option<Template>,
+    /// Generate the ids referencing other objects as typed ids.
+    pub typed_ids: bool,
+}
+
+impl Generator for ViewsGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let groups = create_views(ir, self.typed_ids, report);
+        let context = Context::new().list("groups", groups);
+        write!(out, "{}", template::render_or_default(self.template.as_ref(), "templates/views.tpl", DEFAULT_TEMPLATE, &context)?)?;
+        Ok(())
+    }
+}
+
+
+/// Creates the template variables of the info_method! and info_with_view! macro calls of each group.
+/// Groups without entries are skipped and recorded into `report`.
+fn create_views(ir: &Ir, typed_ids: bool, report: &mut Report) -> Vec<Context> {
+    let mut groups = vec![];
+    for view_group in &ir.view_groups {
+        let (class, item) = (view_group.class.to_lowercase(), view_group.item.as_str());
+        let struct_ = ir.find_struct(&format!("mj{}_", class.to_pascal_case()));
 
         //     info_method! { Model, ffi(), sensor, [
         //     r#type: 1, datatype: 1, needstage: 1,
@@ -43,102 +70,103 @@ pub fn create_views(filepath: &Path) {
 
         // info_with_view!(Data, actuator, [ctrl: MjtNum], [act: MjtNum], M: Deref<Target = MjModel>);
         let mut attribute_types_and_names = vec![];
-
-        /* Parse individual X(..) */
-        for line in capture_data.lines().skip(1) {  // skip the #define line
-            // Remove parentheses, trim whitespace and parse into parts.
-            let mut parts: Vec<_> = line.split(",").map(|item| item.trim()).collect();
-            *parts.first_mut().unwrap() = parts.first().unwrap().split_once("(").unwrap().1.trim();
-
-            // Split at last ). Multiple ) can appear due to MJ_M(...).
-            *parts.last_mut().unwrap() = parts.last().unwrap().rsplit_once(")").unwrap().0.trim();
-
-            if let [type_, prefix, attribute, ntotaldim, dim] = &parts[..] {
-                let type_ = match *type_ {
-                    "float" => "f32".to_string(),
-                    "double" => "f64".to_string(),
-                    "int" => "i32".to_string(),
-                    _ if type_.starts_with("mjt") => type_.to_pascal_case(),
-                    _ => type_.to_string(),
-                };
-
-                // Some attributes may have _ added in front of them instead of at the prefix.
-                // This ensures our view attributes don't add _ to the attribute name but instead add the _
-                // to the prefix end.
-                let (extra_prefix, attribute) = if attribute.starts_with("_") {
-                    ("_", attribute.strip_prefix("_").unwrap())
-                } else { ("", *attribute) };
-
-                let prefix = prefix.trim();
-                let prefix_str = if !prefix.is_empty() {
-                    format!("[{prefix}{extra_prefix}] ")
-                } else {
-                    "".to_string()
-                };
-
-                let cast_str = if type_.starts_with("Mjt") &&
-                    type_ != "MjtByte" &&
-                    type_ != "MjtSize" &&
-                    type_ != "MjtNum"
-                {
-                    " [cast]"
-                } else {
-                    ""
-                };
-
-                // Exceptions: reserved keywords cannot be attribute names
-                let attribute_extra =  if attribute == "type" {
-                     "r#"
-                } else { "" };
-
-                attribute_types_and_names.push(format!("{prefix_str}{attribute_extra}{attribute}: {type_}{cast_str}"));
-
-                // Match the number of dimensions string to correct mapping address array in MjModel
-                if dim.starts_with("MJ_M(") {
-                    let (left, mut right) = dim.strip_prefix("MJ_M(").unwrap().split_once(")").unwrap();
-                    right = right.trim();
-                    external_length_attributes_lengths.push(format!("{attribute_extra}{attribute}: {left}{right}"));
-                } else if ntotaldim.len() > 2 || NX_ALLOWED_DIRECT_LENGTH.contains(ntotaldim) {
-                    fixed_length_attributes_lengths.push(format!("{attribute_extra}{attribute}: {dim}"));
-                } else {
-                    dynamic_length_attributes_lengths.push(format!("{attribute_extra}{attribute}: {ntotaldim}"));
-                }
-            }
+        let mut entries = vec![];
+
+        /* Process individual X(..) */
+        for ViewEntry { c_type: type_, prefix, attribute, ntotaldim, dim } in &view_group.entries {
+            let type_ = match type_.as_str() {
+                "float" => "f32".to_string(),
+                "double" => "f64".to_string(),
+                "int" => "i32".to_string(),
+                _ if type_.starts_with("mjt") => type_.to_pascal_case(),
+                _ => type_.to_string(),
+            };
+            let id_type = if typed_ids && type_ == "i32" {
+                let field_name = format!("{}{attribute}", prefix.trim());
+                let comment = struct_.and_then(|s| s.flat_fields().into_iter().find(|f| f.name == field_name)).map(|f| f.comment.as_str());
+                referenced_id_type(prefix.trim(), attribute, comment.unwrap_or_default())
+            } else {
+                None
+            };
+            let type_ = match id_type {
+                Some(id_type) => id_type.to_string(),
+                None => type_,
+            };
+
+            // Some attributes may have _ added in front of them instead of at the prefix.
+            // This ensures our view attributes don't add _ to the attribute name but instead add the _
+            // to the prefix end.
+            let (extra_prefix, attribute) = match attribute.strip_prefix("_") {
+                Some(attribute) => ("_", attribute),
+                None => ("", attribute.as_str()),
+            };
+
+            let prefix = prefix.trim();
+            let prefix_str = if !prefix.is_empty() {
+                format!("[{prefix}{extra_prefix}] ")
+            } else {
+                "".to_string()
+            };
+
+            let cast_str = if id_type.is_some() || type_.starts_with("Mjt") &&
+                type_ != "MjtByte" &&
+                type_ != "MjtSize" &&
+                type_ != "MjtNum"
+            {
+                " [cast]"
+            } else {
+                ""
+            };
+
+            // Exceptions: reserved keywords cannot be attribute names
+            let attribute_extra =  if attribute == "type" {
+                 "r#"
+            } else { "" };
+
+            attribute_types_and_names.push(format!("{prefix_str}{attribute_extra}{attribute}: {type_}{cast_str}"));
+
+            // Match the number of dimensions string to correct mapping address array in MjModel
+            let (lengths, length, kind) = if let Some((left, right)) = dim.strip_prefix("MJ_M(").and_then(|d| d.split_once(")")) {
+                let right = right.trim();
+                (&mut external_length_attributes_lengths, format!("{left}{right}"), "external")
+            } else if ntotaldim.len() > 2 || NX_ALLOWED_DIRECT_LENGTH.contains(&ntotaldim.as_str()) {
+                (&mut fixed_length_attributes_lengths, dim.clone(), "fixed")
+            } else {
+                (&mut dynamic_length_attributes_lengths, ntotaldim.clone(), "dynamic")
+            };
+            lengths.push(format!("{attribute_extra}{attribute}: {length}"));
+            entries.push(Context::new()
+                .text("name", format!("{attribute_extra}{attribute}"))
+                .text("prefix", if prefix.is_empty() { String::new() } else { format!("{prefix}{extra_prefix}") })
+                .text("type", type_.as_str())
+                .text("length", length)
+                .flag("cast", !cast_str.is_empty())
+                .flag("fixed", kind == "fixed")
+                .flag("external", kind == "external")
+                .flag("dynamic", kind == "dynamic"));
         }
 
         if fixed_length_attributes_lengths.len() + dynamic_length_attributes_lengths.len() + external_length_attributes_lengths.len() > 0 {
-            let class = class.to_pascal_case();
-            let item = item.to_lowercase();
-            info_method_calls.push(format!(
-                "info_method! {{ {class}, ffi(), {item},\
-                    \n    [{}],\
-                    \n    [{}],\
-                    \n    [{}]\
-                    \n}}",
-                join_attributes_chunked_pretty(&fixed_length_attributes_lengths, 3),
-                join_attributes_chunked_pretty(&external_length_attributes_lengths, 3),
-                join_attributes_chunked_pretty(&dynamic_length_attributes_lengths, 3),
-            ));
-
-            // Generate info and view structs. Here we assume all attributes are mandatory
+            // Info and view structs are generated assuming all attributes are mandatory
             // as there is no way to check this here (MANUAL CHECK REQUIRED!).
-            info_with_view_calls.push(format!(
-                "info_with_view!({class}, {item},\n    [{}],\n    []{});",
-                join_attributes_chunked_pretty(&attribute_types_and_names, 10),
-                if class == "Data" {
-                    ", M: Deref<Target = MjModel>"  // MjData has this trait bound.
-                } else { "" }  // MjModel and others have no trait bound.
-            ));
+            // MjData has the `M: Deref<Target = MjModel>` trait bound, MjModel and others have none.
+            let class = class.to_pascal_case();
+            groups.push(Context::new()
+                .text("class", class.as_str())
+                .text("item", item.to_lowercase())
+                .flag("data", class == "Data")
+                .text("fixed_lengths", join_attributes_chunked_pretty(&fixed_length_attributes_lengths, 3))
+                .text("external_lengths", join_attributes_chunked_pretty(&external_length_attributes_lengths, 3))
+                .text("dynamic_lengths", join_attributes_chunked_pretty(&dynamic_length_attributes_lengths, 3))
+                .text("attributes", join_attributes_chunked_pretty(&attribute_types_and_names, 10))
+                .list("entries", entries));
+        }
+        else {
+            report.skip("view group", format!("MJ{}_{}", view_group.class, view_group.item), "no entries");
         }
     }
 
-    for info_method_call in &info_method_calls {
-        println!("{info_method_call}\n");
-    }
-
-    for info_with_view_call in &info_with_view_calls {
-        println!("{info_with_view_call}\n");
-    }
+    groups
 }
 
 /// Joins chunks of text elements together in such way that it ends up
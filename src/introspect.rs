@@ -0,0 +1,431 @@
+//! Module for parsing MuJoCo's Python introspect metadata (python/mujoco/introspect), as an alternative
+//! to parsing the C headers. The files (functions.py, structs.py and enums.py) are Python literals,
+//! which are parsed without a Python interpreter into the same [IR](crate::ir).
+//! The exact extents of pointer fields (`array_extent`) are appended to the field comments in the header
+//! notation (e.g., `(nbody x 3)`), which is what the generators read.
+use std::path::Path;
+use std::sync::LazyLock;
+use regex::Regex;
+
+use crate::diagnostic::{Diagnostic, Source};
+use crate::ir::{Enum, EnumVariant, Field, Function, Ir, Param, Struct};
+
+
+static DECLARATIONS_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^(FUNCTIONS|STRUCTS|ENUMS)\b[^=\n]*=").unwrap());
+
+
+/// A Python literal value.
+#[derive(Debug, Clone, PartialEq)]
+enum Value<'a> {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+    None,
+    /// A name, which is not called (e.g., a constant).
+    Name(&'a str),
+    /// A tuple or a list.
+    Sequence(Vec<Value<'a>>),
+    /// A call (e.g., `ValueType(name='int')`), whose `name` is a slice of the source, locating the call.
+    Call { name: &'a str, args: Vec<Value<'a>>, kwargs: Vec<(&'a str, Value<'a>)> },
+}
+
+impl<'a> Value<'a> {
+    /// Returns the keyword argument `key` of a call.
+    fn kwarg(&self, key: &str) -> Option<&Value<'a>> {
+        match self {
+            Value::Call { kwargs, .. } => kwargs.iter().find(|(k, _)| *k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_sequence(&self) -> &[Value<'a>] {
+        match self {
+            Value::Sequence(items) => items,
+            _ => &[],
+        }
+    }
+
+    /// Returns a dimension (e.g., 3 or 'nbody') as a string.
+    fn as_dim(&self) -> Option<String> {
+        match self {
+            Value::Int(i) => Some(i.to_string()),
+            Value::Str(s) => Some(s.clone()),
+            Value::Name(name) => Some(name.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of `dict([(key, value), ...])`.
+    fn dict_items(&self) -> Vec<(&Value<'a>, &Value<'a>)> {
+        match self {
+            Value::Call { name: "dict", args, .. } => args.first().map_or(&[][..], |a| a.as_sequence()).iter()
+                .filter_map(|item| match item.as_sequence() {
+                    [key, value] => Some((key, value)),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+
+/// A syntax error at `part` of the source.
+type ParseError<'a> = (&'a str, String);
+
+
+/// A recursive descent parser of the Python literals used by the introspect files.
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    /// Skips whitespace, comments and line continuations.
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '\\');
+            self.position += rest.len() - trimmed.len();
+            if trimmed.starts_with('#') {
+                self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+            }
+            else {
+                break;
+            }
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError<'a> {
+        let rest = self.rest();
+        let end = rest.char_indices().nth(1).map_or(rest.len(), |(i, _)| i);
+        (&rest[..end], message.into())
+    }
+
+    /// Consumes `token` (after whitespace) if it's next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else { false }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError<'a>> {
+        if self.eat(token) { Ok(()) } else { Err(self.error(format!("expected `{token}`"))) }
+    }
+
+    fn value(&mut self) -> Result<Value<'a>, ParseError<'a>> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        match rest.chars().next() {
+            Some('(') => {
+                self.position += 1;
+                let (items, trailing_comma) = self.items(")")?;
+                // A parenthesized expression is not a tuple
+                if items.len() == 1 && !trailing_comma { Ok(items.into_iter().next().unwrap()) } else { Ok(Value::Sequence(items)) }
+            }
+            Some('[') => {
+                self.position += 1;
+                Ok(Value::Sequence(self.items("]")?.0))
+            }
+            Some('\'' | '"') => {
+                // Adjacent string literals are concatenated
+                let mut string = self.string()?;
+                while self.rest().trim_start().starts_with(['\'', '"']) {
+                    self.skip_whitespace();
+                    string += &self.string()?;
+                }
+                Ok(Value::Str(string))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let length = rest[1..].find(|c: char| !c.is_ascii_digit()).map_or(rest.len(), |i| i + 1);
+                let number = &rest[..length];
+                self.position += length;
+                number.parse().map(Value::Int).map_err(|_| (number, format!("invalid integer {number}")))
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let length = rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.')).unwrap_or(rest.len());
+                let name = &rest[..length];
+                self.position += length;
+                match name {
+                    "True" => Ok(Value::Bool(true)),
+                    "False" => Ok(Value::Bool(false)),
+                    "None" => Ok(Value::None),
+                    _ if self.eat("(") => self.call(name),
+                    _ => Ok(Value::Name(name)),
+                }
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    /// Parses comma-separated values until `close`, returning them and whether there was a trailing comma.
+    fn items(&mut self, close: &str) -> Result<(Vec<Value<'a>>, bool), ParseError<'a>> {
+        let mut items = vec![];
+        let mut trailing_comma = false;
+        while !self.eat(close) {
+            items.push(self.value()?);
+            trailing_comma = self.eat(",");
+            if !trailing_comma {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok((items, trailing_comma))
+    }
+
+    /// Parses the arguments of a call of `name`, after the opening parenthesis.
+    fn call(&mut self, name: &'a str) -> Result<Value<'a>, ParseError<'a>> {
+        let (mut args, mut kwargs) = (vec![], vec![]);
+        while !self.eat(")") {
+            self.skip_whitespace();
+            let rest = self.rest();
+            let length = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+            let after = rest[length..].trim_start();
+            if length > 0 && after.starts_with('=') && !after.starts_with("==") {
+                self.position += length;
+                self.expect("=")?;
+                kwargs.push((&rest[..length], self.value()?));
+            }
+            else {
+                args.push(self.value()?);
+            }
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        Ok(Value::Call { name, args, kwargs })
+    }
+
+    /// Returns the whitespace preceding the current position on its line.
+    fn indentation(&self) -> &'a str {
+        let line_start = self.text[..self.position].rfind('\n').map_or(0, |i| i + 1);
+        &self.text[line_start..self.position]
+    }
+
+    /// Moves to the next line starting with `indentation` and an opening parenthesis (the next entry of a dict),
+    /// returning `false` if there is none before the end of the dict (a line with less indentation).
+    fn next_entry(&mut self, indentation: &str) -> bool {
+        let mut line_start = self.position;
+        while let Some(i) = self.text[line_start..].find('\n') {
+            line_start += i + 1;
+            let line = &self.text[line_start..];
+            if let Some(rest) = line.strip_prefix(indentation) && rest.starts_with('(') {
+                self.position = line_start;
+                return true;
+            }
+            let trimmed = line.trim_start_matches([' ', '\t']);
+            let line_indentation = line.len() - trimmed.len();
+            if line_indentation < indentation.len() && !trimmed.starts_with(['\n', '#']) {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Parses a single or double quoted string literal, including triple-quoted ones.
+    fn string(&mut self) -> Result<String, ParseError<'a>> {
+        let rest = self.rest();
+        let quote = if rest.starts_with("'''") || rest.starts_with("\"\"\"") { &rest[..3] } else { &rest[..1] };
+        let mut string = String::new();
+        let mut chars = rest[quote.len()..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            if rest[quote.len() + i..].starts_with(quote) {
+                self.position += quote.len() * 2 + i;
+                return Ok(string);
+            }
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, 't')) => string.push('\t'),
+                    Some((_, '\n')) => {}
+                    Some((_, c)) => string.push(c),
+                    None => break,
+                },
+                '\n' if quote.len() == 1 => break,
+                c => string.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+}
+
+
+/// A C type described by introspect (e.g., `PointerType(inner_type=ValueType(name='mjtNum', is_const=True))`).
+#[derive(Default)]
+struct CType {
+    name: String,
+    is_const: bool,
+    pointer_depth: usize,
+    extents: Vec<String>,
+    /// Fields of an anonymous struct or union.
+    nested: Option<Vec<Field>>,
+    is_union: bool,
+}
+
+
+/// Converts the type description `value`. Anonymous structs are named `{parent}__bindgen_ty_{n}`.
+fn c_type(value: &Value, parent: &str, n_anonymous: &mut usize) -> Result<CType, String> {
+    let Value::Call { name, .. } = value else { return Err("expected a type".to_string()) };
+    match *name {
+        "ValueType" => Ok(CType {
+            name: value.kwarg("name").and_then(Value::as_str).ok_or("ValueType without a name")?.to_string(),
+            is_const: value.kwarg("is_const") == Some(&Value::Bool(true)),
+            ..Default::default()
+        }),
+        "PointerType" => {
+            let mut inner = c_type(value.kwarg("inner_type").ok_or("PointerType without an inner type")?, parent, n_anonymous)?;
+            inner.pointer_depth += 1;
+            Ok(inner)
+        }
+        "ArrayType" => {
+            let mut inner = c_type(value.kwarg("inner_type").ok_or("ArrayType without an inner type")?, parent, n_anonymous)?;
+            let extents = value.kwarg("extents").map_or(&[][..], Value::as_sequence).iter()
+                .map(|e| e.as_dim().ok_or("invalid array extent")).collect::<Result<Vec<_>, _>>()?;
+            inner.extents = extents.into_iter().chain(inner.extents).collect();
+            Ok(inner)
+        }
+        "AnonymousStructDecl" | "AnonymousUnionDecl" => {
+            *n_anonymous += 1;
+            let is_union = *name == "AnonymousUnionDecl";
+            let name = format!("{parent}__bindgen_ty_{n_anonymous}");
+            let fields = fields(value.kwarg("fields").map_or(&[][..], Value::as_sequence), &name)?;
+            Ok(CType { name, nested: Some(fields), is_union, ..Default::default() })
+        }
+        _ => Err(format!("unknown type {name}")),
+    }
+}
+
+
+/// Converts the `StructFieldDecl`s of the struct (or anonymous struct) `parent`.
+fn fields(declarations: &[Value], parent: &str) -> Result<Vec<Field>, String> {
+    let mut n_anonymous = 0;
+    declarations.iter().map(|declaration| {
+        let name = declaration.kwarg("name").and_then(Value::as_str).ok_or("field without a name")?;
+        let CType { name: c_type, is_const, pointer_depth, extents, nested, is_union } =
+            c_type(declaration.kwarg("type").ok_or("field without a type")?, parent, &mut n_anonymous)?;
+        let mut comment = declaration.kwarg("doc").and_then(Value::as_str).unwrap_or_default().to_string();
+        let array_extent: Vec<_> = declaration.kwarg("array_extent").map_or(&[][..], Value::as_sequence).iter()
+            .filter_map(Value::as_dim).collect();
+        // The headers note the extents of one-dimensional arrays as (n x 1)
+        match &array_extent[..] {
+            [] => {}
+            [len] => comment = format!("{comment} ({len} x 1)"),
+            _ => comment = format!("{comment} ({})", array_extent.join(" x ")),
+        }
+        Ok(Field {
+            name: name.to_string(), c_type, is_const, pointer_depth, array_dims: extents, comment, nested, is_union,
+            section: String::new()
+        })
+    }).collect()
+}
+
+
+/// Converts a `FunctionDecl`.
+fn function(declaration: &Value) -> Result<Function, String> {
+    let name = declaration.kwarg("name").and_then(Value::as_str).ok_or("function without a name")?;
+    let return_type = c_type(declaration.kwarg("return_type").ok_or("function without a return type")?, name, &mut 0)?;
+    let return_type = format!(
+        "{}{}{}", if return_type.is_const { "const " } else { "" }, return_type.name, "*".repeat(return_type.pointer_depth)
+    );
+    let mut params = vec![];
+    for parameter in declaration.kwarg("parameters").map_or(&[][..], Value::as_sequence) {
+        let param_name = parameter.kwarg("name").and_then(Value::as_str).ok_or("parameter without a name")?;
+        let CType { name: c_type, is_const, pointer_depth, extents, .. } =
+            c_type(parameter.kwarg("type").ok_or("parameter without a type")?, name, &mut 0)?;
+        let array_len = if extents.is_empty() { None } else { Some(extents.join("*")) };
+        params.push(Param { name: param_name.to_string(), c_type, is_const, pointer_depth, array_len });
+    }
+    let doc = declaration.kwarg("doc").and_then(Value::as_str).unwrap_or_default();
+    Ok(Function {
+        name: name.to_string(), return_type, params,
+        doc: doc.lines().map(|line| format!(" {line}")).collect(), section: String::new()
+    })
+}
+
+
+/// Converts a `StructDecl`, named after its declaration (e.g., `struct mjModel_`).
+fn struct_(declaration: &Value) -> Result<Struct, String> {
+    let name = declaration.kwarg("declname").and_then(Value::as_str)
+        .and_then(|d| d.strip_prefix("struct ")).or_else(|| declaration.kwarg("name").and_then(Value::as_str))
+        .ok_or("struct without a name")?;
+    Ok(Struct { name: name.to_string(), fields: fields(declaration.kwarg("fields").map_or(&[][..], Value::as_sequence), name)? })
+}
+
+
+/// Converts an `EnumDecl`. Counting variants (starting with mjN) are excluded.
+fn enum_(declaration: &Value) -> Result<Enum, String> {
+    let name = declaration.kwarg("name").and_then(Value::as_str).ok_or("enum without a name")?;
+    let mut variants = vec![];
+    for (variant_name, value) in declaration.kwarg("values").ok_or("enum without values")?.dict_items() {
+        let (Value::Str(variant_name), Value::Int(value)) = (variant_name, value) else { return Err(format!("invalid variant of {name}")) };
+        if !variant_name.starts_with("mjN") {
+            variants.push(EnumVariant { name: variant_name.clone(), value: *value });
+        }
+    }
+    Ok(Enum { name: name.to_string(), variants })
+}
+
+
+/// Parses the FUNCTIONS, STRUCTS and ENUMS declarations of an introspect file.
+/// Each declaration is a `dict([(name, *Decl(...)), ...])`, whose entries are parsed one by one: after a syntax
+/// error, the entry is reported and skipped, and parsing resumes at the next entry (the next line with the indentation
+/// of the first entry), so the other entries of the dict survive. Entries that can't be converted are reported and skipped.
+pub fn parse_introspect(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Ir {
+    let mut ir = Ir::default();
+    for capture in DECLARATIONS_REGEX.captures_iter(&source.text) {
+        let mut parser = Parser { text: &source.text, position: capture.get(0).unwrap().end() };
+        if let Err((part, message)) = parser.expect("dict").and_then(|_| parser.expect("(")).and_then(|_| parser.expect("[")) {
+            diagnostics.push(source.warning(part, format!("{message}, skipping {}", &capture[1])));
+            continue;
+        }
+        parser.skip_whitespace();
+        let indentation = parser.indentation();
+
+        while !parser.eat("]") {
+            let entry = parser.value().and_then(|entry| {
+                if !parser.eat(",") && !parser.rest().trim_start().starts_with(']') {
+                    return Err(parser.error("expected `,` or `]`"));
+                }
+                Ok(entry)
+            });
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err((part, message)) => {
+                    diagnostics.push(source.warning(part, format!("{message}, skipping the declaration")));
+                    if parser.next_entry(indentation) { continue } else { break }
+                }
+            };
+
+            let [_, declaration @ Value::Call { name: call_name, .. }] = entry.as_sequence() else { continue };
+            let converted = match &capture[1] {
+                "FUNCTIONS" => function(declaration).map(|f| ir.functions.push(f)),
+                "STRUCTS" => struct_(declaration).map(|s| ir.structs.push(s)),
+                _ => enum_(declaration).map(|e| ir.enums.push(e)),
+            };
+            if let Err(message) = converted {
+                diagnostics.push(source.warning(call_name, format!("{message}, skipping the declaration")));
+            }
+        }
+    }
+    ir
+}
+
+
+/// Returns `true` if the path is an introspect file (a Python file) instead of a C header.
+pub fn is_introspect(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "py")
+}
@@ -0,0 +1,246 @@
+//! Intermediate representation (IR) of MuJoCo's API, shared by all the generators.
+//! The IR is created by the parsers in [`crate::parse`].
+
+
+/// Everything parsed from MuJoCo's headers and documentation.
+#[derive(Debug, Clone, Default)]
+pub struct Ir {
+    /// Functions declared with MJAPI (e.g., in mujoco.h).
+    pub functions: Vec<Function>,
+    /// Structs (e.g., in structs.h or mjmodel.h).
+    pub structs: Vec<Struct>,
+    /// Enums (e.g., in mjmodel.h).
+    pub enums: Vec<Enum>,
+    /// Groups of views into MjModel/MjData arrays (in indexer_xmacro.h).
+    pub view_groups: Vec<ViewGroup>,
+    /// Documented types (in APItypes.rst).
+    pub type_docs: Vec<TypeDoc>,
+    /// Function pointer types (e.g., mjfGeneric in mjdata.h), as functions named after the type.
+    pub function_types: Vec<Function>,
+    /// Global callbacks, declared with MJAPI extern (e.g., mjcb_control in mujoco.h).
+    pub callbacks: Vec<Callback>,
+}
+
+impl Ir {
+    /// Appends everything from the `other` IR.
+    pub fn extend(&mut self, other: Ir) {
+        self.functions.extend(other.functions);
+        self.structs.extend(other.structs);
+        self.enums.extend(other.enums);
+        self.view_groups.extend(other.view_groups);
+        self.type_docs.extend(other.type_docs);
+        self.function_types.extend(other.function_types);
+        self.callbacks.extend(other.callbacks);
+    }
+
+    /// Finds the struct with the given C name (e.g., mjOption_).
+    pub fn find_struct(&self, name: &str) -> Option<&Struct> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
+    /// Finds the enum with the given C name (e.g., mjtIntegrator).
+    pub fn find_enum(&self, name: &str) -> Option<&Enum> {
+        self.enums.iter().find(|e| e.name == name)
+    }
+
+    /// Returns the signature of the callback: its inline signature or its function pointer type.
+    pub fn callback_signature<'a>(&'a self, callback: &'a Callback) -> Option<&'a Function> {
+        match &callback.signature {
+            CallbackType::Named(name) => self.function_types.iter().find(|f| f.name == *name),
+            CallbackType::Inline(signature) => Some(signature),
+        }
+    }
+}
+
+
+/// A function declared with MJAPI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    /// The return type as written in the header, including qualifiers (e.g., const char*).
+    pub return_type: String,
+    pub params: Vec<Param>,
+    /// Lines of the documentation comment, without the leading `//`.
+    pub doc: Vec<String>,
+    /// The title of the header section declaring the function (e.g., Virtual file system), empty if none.
+    pub section: String,
+}
+
+/// A parameter of a [`Function`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    /// The type, without the const qualifier and pointers (e.g., mjtNum).
+    pub c_type: String,
+    pub is_const: bool,
+    /// Number of pointer indirections.
+    pub pointer_depth: usize,
+    /// The array length expression (e.g., 3 or mjNREF), when declared as an array.
+    pub array_len: Option<String>,
+}
+
+impl Param {
+    pub fn is_pointer(&self) -> bool {
+        self.pointer_depth > 0
+    }
+}
+
+
+/// A global callback, i.e., a function pointer variable declared with MJAPI extern (e.g., mjcb_control).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Callback {
+    pub name: String,
+    pub signature: CallbackType,
+}
+
+/// The type of a [`Callback`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallbackType {
+    /// A function pointer type (e.g., mjfGeneric) of [`Ir::function_types`].
+    Named(String),
+    /// A signature declared inline (e.g., `void (*mju_user_error)(const char*)`), as a function named after the callback.
+    Inline(Function),
+}
+
+
+/// A struct and its fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Struct {
+    /// The C name of the struct (e.g., mjModel_).
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+impl Struct {
+    /// Returns the fields, with fields of nested structs in place of the nested struct, in the order of declaration.
+    pub fn flat_fields(&self) -> Vec<&Field> {
+        self.flat_field_paths().into_iter().map(|(_, field)| field).collect()
+    }
+
+    /// Returns the fields like [`Self::flat_fields`], along with their paths from the struct (e.g., global.fovy).
+    pub fn flat_field_paths(&self) -> Vec<(String, &Field)> {
+        fn flatten<'a>(fields: &'a [Field], parent: &str, out: &mut Vec<(String, &'a Field)>) {
+            for field in fields {
+                let path = if parent.is_empty() { field.name.clone() } else { format!("{parent}.{}", field.name) };
+                match &field.nested {
+                    Some(nested) => flatten(nested, &path, out),
+                    None => out.push((path, field)),
+                }
+            }
+        }
+
+        let mut out = vec![];
+        flatten(&self.fields, "", &mut out);
+        out
+    }
+}
+
+/// A field of a [`Struct`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    /// The type, without the const qualifier and pointers (e.g., mjtNum).
+    /// Anonymous nested structs are named as bindgen names them, i.e., `{parent}__bindgen_ty_{n}`.
+    pub c_type: String,
+    pub is_const: bool,
+    /// Number of pointer indirections.
+    pub pointer_depth: usize,
+    /// Array dimension expressions, outermost first (e.g., \[mjMAXLINE, 2*mjMAXLINEPNT\]).
+    /// Empty when not declared as an array.
+    pub array_dims: Vec<String>,
+    /// The trailing comment, without the leading `//`.
+    pub comment: String,
+    /// Fields of an anonymous nested struct or union.
+    pub nested: Option<Vec<Field>>,
+    /// Whether the nested fields are members of a union, i.e., all start at the same offset.
+    pub is_union: bool,
+    /// The preceding standalone comment of the struct body (e.g., bodies), empty if none.
+    pub section: String,
+}
+
+impl Field {
+    pub fn is_pointer(&self) -> bool {
+        self.pointer_depth > 0
+    }
+
+    /// Returns the array length of a one-dimensional array field.
+    pub fn array_len(&self) -> Option<&str> {
+        match &self.array_dims[..] {
+            [len] => Some(len),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the field holds a single value (not a pointer, array or nested struct).
+    pub fn is_scalar(&self) -> bool {
+        !self.is_pointer() && self.array_dims.is_empty() && self.nested.is_none()
+    }
+}
+
+
+/// An enum and its variants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+    /// The C name of the enum (e.g., mjtIntegrator).
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+}
+
+/// A variant of an [`Enum`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
+    /// The C name of the variant (e.g., mjINT_EULER).
+    pub name: String,
+    pub value: i64,
+}
+
+impl Enum {
+    /// Returns short, lowercase names of the variants, obtained by removing the prefix
+    /// common to all variants (e.g., mjINT_EULER and mjINT_RK4 become euler and rk4).
+    pub fn short_variant_names(&self) -> Vec<String> {
+        let prefix_len = match &self.variants[..] {
+            [] => 0,
+            [single] => single.name.find('_').map_or(0, |i| i + 1),
+            [first, rest @ ..] => {
+                // Common prefix, cut back to the last underscore.
+                let common = rest.iter().fold(first.name.len(), |len, v| {
+                    first.name.bytes().zip(v.name.bytes()).take(len).take_while(|(a, b)| a == b).count()
+                });
+                first.name[..common].rfind('_').map_or(0, |i| i + 1)
+            }
+        };
+        self.variants.iter().map(|v| v.name[prefix_len..].to_lowercase()).collect()
+    }
+}
+
+
+/// A group of views, defined with an X-macro (e.g., MJMODEL_BODY).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewGroup {
+    /// The class of the group, as in the macro name (e.g., MODEL).
+    pub class: String,
+    /// The item of the group, as in the macro name (e.g., BODY).
+    pub item: String,
+    pub entries: Vec<ViewEntry>,
+}
+
+/// A single `X(type, prefix, attribute, ntotaldim, dim)` entry of a [`ViewGroup`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewEntry {
+    pub c_type: String,
+    pub prefix: String,
+    pub attribute: String,
+    /// The number of items (e.g., nbody).
+    pub ntotaldim: String,
+    /// The length of an item (e.g., 3 or MJ_M(jnt_qposadr)).
+    pub dim: String,
+}
+
+
+/// A type documented in the API reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDoc {
+    /// The C name of the type (e.g., mjtNum).
+    pub name: String,
+    pub doc: String,
+}
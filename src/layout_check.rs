@@ -0,0 +1,166 @@
+//! Module for generating compile-time checks of struct layouts against the bindgen bindings (mujoco_c).
+//! The other generators derive field types from the (documentation) headers, thus these checks
+//! ensure a mismatch between the headers and the bindings fails the build.
+use std::io::Write;
+
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::{Field, Ir};
+
+
+/// Rust keywords (strict and reserved, of all editions) that bindgen renames by appending an underscore.
+const RUST_KEYWORDS: [&str; 52] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+    "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+    "trait", "true", "type", "unsafe", "use", "where", "while", "async", "await", "dyn", "abstract", "become", "box",
+    "do", "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "gen",
+];
+
+/// Keywords that can't be raw identifiers.
+const PATH_KEYWORDS: [&str; 4] = ["crate", "self", "Self", "super"];
+
+
+/// A field of a struct, with the type as bindgen generates it.
+struct LayoutField {
+    name: String,
+    rust_type: String,
+}
+
+
+/// Converts a C type (without pointers) to the type bindgen generates.
+fn bindgen_type(c_type: &str) -> String {
+    match c_type {
+        "float" => "std::ffi::c_float".to_string(),
+        "double" => "std::ffi::c_double".to_string(),
+        "char" => "std::ffi::c_char".to_string(),
+        "int" => "std::ffi::c_int".to_string(),
+        "unsigned char" => "std::ffi::c_uchar".to_string(),
+        "unsigned int" => "std::ffi::c_uint".to_string(),
+        "size_t" => "usize".to_string(),
+        "uint64_t" => "u64".to_string(),
+        "int64_t" => "i64".to_string(),
+        "uintptr_t" => "usize".to_string(),
+        "void" => "std::ffi::c_void".to_string(),
+        _ => format!("mujoco_c::{c_type}"),
+    }
+}
+
+
+/// Converts the fields to the types bindgen generates.
+/// Unnamed nested structs and unions are named as bindgen names them, i.e., `__bindgen_anon_{n}`.
+fn layout_fields(fields: &[Field]) -> Vec<LayoutField> {
+    let mut n_unnamed = 0;
+    fields.iter().map(|field| {
+        if field.nested.is_some() {
+            let name = if field.name.is_empty() {
+                n_unnamed += 1;
+                format!("__bindgen_anon_{n_unnamed}")
+            } else { bindgen_name(&field.name) };
+            return LayoutField { name, rust_type: format!("mujoco_c::{}", field.c_type) };
+        }
+        LayoutField { name: bindgen_name(&field.name), rust_type: bindgen_field_type(field) }
+    }).collect()
+}
+
+
+/// Returns the type bindgen generates for a (non-nested) field, including pointers and arrays.
+pub(crate) fn bindgen_field_type(field: &Field) -> String {
+    let Field { c_type, is_const, pointer_depth, array_dims, .. } = field;
+    let mut rust_type = bindgen_type(c_type);
+    for i in 0..*pointer_depth {
+        // Only the innermost pointer is const.
+        rust_type = format!("{} {rust_type}", if *is_const && i == 0 { "*const" } else { "*mut" });
+    }
+
+    for size in array_dims.iter().rev() {  // the innermost array is the last dimension
+        let type_cast = if size.chars().all(char::is_numeric) { "" } else { " as usize" };
+        let size = if size.starts_with("mj") { format!("mujoco_c::{size}") } else { size.to_string() };
+        rust_type = format!("[{rust_type}; {size}{type_cast}]");
+    }
+    rust_type
+}
+
+
+/// Collects the layout fields of the nested structs and unions in `fields`, with the innermost first,
+/// along with whether they are unions.
+fn nested_layouts(fields: &[Field], out: &mut Vec<(String, Vec<LayoutField>, bool)>) {
+    for field in fields {
+        if let Some(nested) = &field.nested {
+            nested_layouts(nested, out);
+            out.push((field.c_type.clone(), layout_fields(nested), field.is_union));
+        }
+    }
+}
+
+
+/// Returns the name of the field as generated by bindgen.
+pub(crate) fn bindgen_name(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) { format!("{name}_") } else { name.to_string() }
+}
+
+
+/// Returns the name as a Rust identifier, i.e., a raw identifier for keywords (e.g., r#type),
+/// except for the keywords that can't be raw identifiers, which are renamed as by bindgen (e.g., self_).
+pub(crate) fn rust_ident(name: &str) -> String {
+    if PATH_KEYWORDS.contains(&name) { bindgen_name(name) }
+    else if RUST_KEYWORDS.contains(&name) { format!("r#{name}") }
+    else { name.to_string() }
+}
+
+
+/// Creates the layout check of a single struct or union.
+/// Members of a union all start at offset 0, thus are not checked for ordering.
+fn struct_layout_check(struct_name: &str, fields: &[LayoutField], is_union: bool) -> String {
+    let mut checks = vec![format!("    type S = mujoco_c::{struct_name};")];
+    for LayoutField { name, rust_type } in fields {
+        // Borrowing a union field is unsafe, though only the type is checked here
+        let access = if is_union { format!("unsafe {{ &s.{name} }}") } else { format!("&s.{name}") };
+        checks.push(format!("    let _: fn(&S) -> &{rust_type} = |s| {access};"));
+    }
+
+    if is_union {
+        for LayoutField { name, .. } in fields {
+            checks.push(format!("    assert!(offset_of!(S, {name}) == 0, \"{struct_name}: {name} must start the union\");"));
+        }
+    }
+    else {
+        for pair in fields.windows(2) {
+            let (first, second) = (&pair[0].name, &pair[1].name);
+            checks.push(format!(
+                "    assert!(offset_of!(S, {first}) < offset_of!(S, {second}), \"{struct_name}: {first} must precede {second}\");"
+            ));
+        }
+    }
+
+    format!("/* {struct_name} */\nconst _: () = {{\n{}\n}};", checks.join("\n"))
+}
+
+
+/// Generates compile-time checks of field types and ordering of structs (including their nested structs)
+/// against the bindgen bindings.
+pub struct LayoutCheckGenerator {
+    /// C names of the structs to check (e.g., mjModel_). When empty, all structs are checked.
+    pub struct_names: Vec<String>,
+}
+
+impl Generator for LayoutCheckGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        writeln!(out, "use core::mem::offset_of;\n")?;
+        for struct_ in &ir.structs {
+            if !self.struct_names.is_empty() && !self.struct_names.contains(&struct_.name) {
+                continue;
+            }
+
+            let mut layouts = vec![(struct_.name.clone(), layout_fields(&struct_.fields), false)];
+            nested_layouts(&struct_.fields, &mut layouts);
+            for (name, fields, is_union) in layouts {
+                if fields.is_empty() {
+                    report.skip("struct", name, "no fields were parsed");
+                    continue;
+                }
+                writeln!(out, "{}\n", struct_layout_check(&name, &fields, is_union))?;
+            }
+        }
+        Ok(())
+    }
+}
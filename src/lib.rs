@@ -0,0 +1,50 @@
+//! # MuJoCo-rs-util
+//! Parsers and code generators used in the development of MuJoCo-rs.
+//!
+//! MuJoCo's headers and documentation are parsed (see [`parse`]) into an intermediate representation
+//! (see [`ir::Ir`]), from which the generators (implementors of [`generator::Generator`]) create Rust code.
+//! Alternatively, the API is parsed from MuJoCo's Python introspect metadata (see [`introspect`]).
+//! This allows running the generators from a build script, e.g.:
+//! ```no_run
+//! use mujoco_rs_utils::{diagnostic::Source, generator::Generator, parse, typedef::TypedefGenerator};
+//!
+//! let mut diagnostics = vec![];
+//! let mut ir = parse::parse_header(&Source::read("mujoco/include/mujoco/mujoco.h").unwrap(), &mut diagnostics);
+//! ir.type_docs = parse::parse_type_docs(&Source::read("mujoco/doc/APItypes.rst").unwrap());
+//! for diagnostic in &diagnostics {
+//!     eprintln!("{diagnostic}");
+//! }
+//! let code = TypedefGenerator { prefix: Some("mjt".into()) }.generate_string(&ir).unwrap();
+//! ```
+//! For generating into `OUT_DIR` from a build script, see [`build_script::BuildScript`].
+pub mod ir;
+pub mod parse;
+pub mod introspect;
+pub mod diagnostic;
+pub mod generator;
+pub mod report;
+pub mod constraints;
+pub mod smoke_test;
+pub mod build_script;
+pub mod discovery;
+pub mod template;
+pub mod region;
+pub mod diff;
+
+pub mod getter_setter;
+pub mod fixed_arr_fn;
+pub mod info_method;
+pub mod array_slice;
+pub mod model_fn;
+pub mod derivative;
+pub mod callback;
+pub mod object_id;
+pub mod range;
+pub mod sparse;
+pub mod name_lookup;
+pub mod typedef;
+pub mod builder;
+pub mod serde_impl;
+pub mod layout_check;
+pub mod spec;
+pub mod coverage;
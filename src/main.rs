@@ -1,14 +1,18 @@
 //! # MuJoCo-rs-util
 //! A CLI utility to support some development of MuJoCo-rs.
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+use std::io::{self, IsTerminal};
+use std::process;
 
-mod getter_setter;
-mod fixed_arr_fn;
-mod info_method;
-mod array_slice;
-mod model_fn;
-mod typedef;
+use mujoco_rs_utils::diagnostic::{Diagnostic, Source};
+use mujoco_rs_utils::discovery::MujocoRoot;
+use mujoco_rs_utils::generator::Generator;
+use mujoco_rs_utils::ir::{Enum, Function, Ir, Struct};
+use mujoco_rs_utils::report::Report;
+use mujoco_rs_utils::template::Template;
+use mujoco_rs_utils::{array_slice, builder, callback, constraints, coverage, derivative, diff, fixed_arr_fn, getter_setter, info_method};
+use mujoco_rs_utils::{introspect, layout_check, model_fn, name_lookup, object_id, parse, range, region, serde_impl, sparse, spec, typedef};
 
 
 #[derive(Parser, Debug)]
@@ -16,21 +20,134 @@ mod typedef;
 struct Args {
     /// The command to execute
     #[command(subcommand)]
-    command: Command
+    command: Command,
+
+    /// Print a report of the declarations that were seen but not generated (with reasons) to stderr
+    #[arg(long, global = true, value_enum)]
+    skipped_report: Option<ReportFormat>,
+
+    /// Write the output into this file (e.g., of mujoco-rs) instead of printing it
+    #[arg(long, global = true)]
+    write: Option<PathBuf>,
+
+    /// Replace only the region of the --write file between the `mujoco-rs-utils begin: <REGION>`
+    /// and `mujoco-rs-utils end: <REGION>` marker comments, instead of the whole file
+    #[arg(long, global = true, requires = "write")]
+    region: Option<String>,
+
+    /// Don't write the --write file, print a unified diff of the changes and a summary of the
+    /// new, removed and changed items (e.g., fields and functions) instead
+    #[arg(long, global = true, requires = "write")]
+    dry_run: bool,
+
+    /// When to color the diff of --dry-run
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    color: ColorChoice,
+
+    /// A MuJoCo release or source tree, in which file arguments given only by name (e.g., mujoco.h,
+    /// indexer_xmacro.h, APItypes.rst, references.h, functions.py) are located. Header lists default to include/mujoco/*.h.
+    /// Header arguments may also be introspect files (functions.py, structs.py, enums.py) of python/mujoco/introspect
+    #[arg(long, global = true)]
+    mujoco_root: Option<PathBuf>,
+}
+
+/// Format of the skipped-declaration report.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
+/// When to color the output.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ColorChoice {
+    /// Color when printing to a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+/// A linear algebra library of the fixed-array wrapper layer.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum LinalgLibrary {
+    Nalgebra,
+    Glam,
+}
+
+impl From<LinalgLibrary> for fixed_arr_fn::Linalg {
+    fn from(library: LinalgLibrary) -> Self {
+        match library {
+            LinalgLibrary::Nalgebra => fixed_arr_fn::Linalg::Nalgebra,
+            LinalgLibrary::Glam => fixed_arr_fn::Linalg::Glam,
+        }
+    }
+}
+
+/// Where and how the output of the generator is written.
+struct Output {
+    report_format: Option<ReportFormat>,
+    write: Option<PathBuf>,
+    region: Option<String>,
+    dry_run: bool,
+    color: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 enum Command {
     /// A helper to automatically create calls to macros to facilitate the
     /// the view to MjData/MjModel array.
     CreateViews {
-        indexer_xmacro_path: PathBuf
+        indexer_xmacro_path: PathBuf,
+        /// Path to a template file replacing the default output template of the macro calls (templates/views.tpl).
+        #[arg(long)]
+        template: Option<PathBuf>,
+        /// Generate the ids referencing other objects (e.g., body_parentid) as typed ids (see create-id-types).
+        #[arg(long)]
+        typed_ids: bool,
+        /// Path to the header defining mjModel and mjData (e.g., mjmodel.h). With --typed-ids, the field comments
+        /// are used to infer the referenced objects, which the names don't tell.
+        #[arg(long)]
+        model_header: Option<PathBuf>
+    },
+
+    /// Creates range accessors of MjModel from the address/count arrays (e.g., body_jntadr and body_jntnum)
+    /// of each object group of indexer_xmacro.h, e.g., the joints of a body or the act of an actuator.
+    CreateRangeAccessors {
+        indexer_xmacro_path: PathBuf,
+        /// Paths to the headers defining mjModel and mjData (e.g., mjmodel.h, mjdata.h). The address/count
+        /// fields of mjModel are used along with the ones of the groups, the MjData arrays are found in mjData.
+        #[arg(long, num_args=0..)]
+        headers: Vec<PathBuf>,
+        /// Use the typed ids (see create-id-types) for the ids of objects.
+        #[arg(long)]
+        typed_ids: bool,
+    },
+
+    /// Creates the typed ids of MuJoCo objects (e.g., BodyId), used by the views and array slices with --typed-ids.
+    CreateIdTypes,
+
+    /// Creates name/id lookup methods of MjModel for each object group of indexer_xmacro.h,
+    /// reading the names directly from the names buffer.
+    CreateNameLookups {
+        indexer_xmacro_path: PathBuf,
+        /// Path to the header defining mjModel (e.g., mjmodel.h). When given, groups without
+        /// a name address array (name_*adr) are skipped.
+        #[arg(long)]
+        model_header: Option<PathBuf>
     },
 
     /// Creates Rust wrappers around C MuJoCo functions that have
     /// fixed-sized arrays as parameters.
     CreateFixedArrayFunctionWrappers {
-        mujoco_h_path: PathBuf
+        mujoco_h_path: PathBuf,
+        /// Also create a test module with a smoke test of each wrapper.
+        #[arg(long)]
+        with_tests: bool,
+        /// Also create a module of wrappers using the vector, quaternion and matrix types of this library,
+        /// enabled by the output feature of the same name. May be given multiple times.
+        #[arg(long, value_enum)]
+        linalg: Vec<LinalgLibrary>,
     },
 
     /// Creates method wrappers for functions that potentially and logically
@@ -43,7 +160,35 @@ enum Command {
         
         /// Ignore the methods that contain these types in the parameters.
         #[arg(num_args=0..)]
-        blacklist: Vec<String>
+        blacklist: Vec<String>,
+
+        /// Also create a test module with a smoke test of each method, called on the instance
+        /// created by this expression (e.g., 'MjModel::from_xml_string("<mujoco/>").unwrap()').
+        #[arg(long)]
+        test_instance: Option<String>,
+
+        /// Expression creating the model of the smoke tests, bound to `model` before the instance
+        /// is created (e.g., 'MjModel::from_xml_string("<mujoco/>").unwrap()' with the instance 'MjData::new(&model)').
+        /// Methods taking an mjModel are only tested when given.
+        #[arg(long)]
+        test_model: Option<String>
+    },
+    /// Creates wrappers of the derivative (mjd_) functions, which take optional output matrices
+    /// of the documented shapes (e.g., `A: (2*nv+na x 2*nv+na)`) and validate their lengths.
+    CreateDerivativeWrappers {
+        /// Path to the mujoco.h file.
+        mujoco_h_path: PathBuf,
+        /// Side-car file of shapes replacing the documented ones,
+        /// in the format `<function> <parameter>: (<rows> x <cols>)`.
+        #[arg(long)]
+        shapes: Option<PathBuf>,
+    },
+    /// Creates setters of the global callbacks (e.g., mjcb_control, mju_user_error), which take Rust closures
+    /// and install `extern "C"` trampolines calling them.
+    CreateCallbacks {
+        /// Paths to the headers declaring the callbacks and their function pointer types (e.g., mujoco.h, mjdata.h).
+        #[arg(num_args=0..)]
+        header_paths: Vec<PathBuf>,
     },
     /// Create type redefinitions for types that start with a given string.
     CreateTypes {
@@ -60,7 +205,32 @@ enum Command {
         /// The prefix to add in front of the parsed length variable.
         accessor_prefix: String,
         /// The name of the struct for which to create the slice methods.
-        struct_name: String
+        struct_name: String,
+        /// Also print the generic view types used by multi-dimensional array entries.
+        #[arg(long)]
+        view_types: bool,
+        /// Generate the ids referencing other objects (e.g., body_parentid) as typed ids (see create-id-types).
+        #[arg(long)]
+        typed_ids: bool,
+        /// Path to a template file replacing the default output template of the macro entries (templates/array_slice.tpl).
+        /// The view types (--view-types) are not templated.
+        #[arg(long)]
+        template: Option<PathBuf>
+    },
+
+    /// Creates methods returning views of the sparse (CSR) matrices of a struct (e.g., mjData_), recognized
+    /// by their rownnz, rowadr and colind arrays (e.g., efc_J_rownnz), with row iteration and dense conversion.
+    CreateSparseViews {
+        /// Path to the documentation structs.h file containing the documentation-public structs.
+        structs_filepath: PathBuf,
+        /// The prefix to add in front of the model sizes (e.g., model.ffi()).
+        accessor_prefix: String,
+        /// The name of the struct for which to create the sparse views.
+        #[arg(default_value = "mjData_")]
+        struct_name: String,
+        /// Also print the generic sparse view type.
+        #[arg(long)]
+        view_types: bool,
     },
 
     /// Creates getter setters macro calls for reading and writing to non-array data.
@@ -68,37 +238,398 @@ enum Command {
         /// Path to the documentation structs.h file containing the documentation-public structs.
         structs_filepath: PathBuf,
         /// The name of the struct for which to create the slice methods.
-        struct_name: String
+        struct_name: String,
+        /// Path to the documentation XMLreference.rst file, used to obtain ranges and defaults.
+        #[arg(long)]
+        xml_reference: Option<PathBuf>,
+        /// The XML element whose attributes correspond to the struct's fields.
+        #[arg(long, default_value = "option")]
+        xml_element: String,
+        /// Path to a file overriding ranges and defaults. Each line is either
+        /// `<field> <op> <value>` (op being >, >=, < or <=) or `<field> default <value>`,
+        /// the field being its name or its path through nested structs (e.g., map.haze).
+        #[arg(long)]
+        overrides: Option<PathBuf>,
+        /// Also print the error type returned by validating setters.
+        #[arg(long)]
+        error_type: bool,
+        /// Path to a template file replacing the default output template of the macro entries (templates/getters_setters.tpl).
+        /// The string accessors, validating setters and default values are not templated.
+        #[arg(long)]
+        template: Option<PathBuf>
+    },
+
+    /// Creates a builder type for a MuJoCo struct (e.g., mjOption_), including its nested structs.
+    CreateBuilder {
+        /// Path to the documentation structs.h file containing the documentation-public structs.
+        structs_filepath: PathBuf,
+        /// The name of the struct for which to create the builder.
+        struct_name: String,
+        /// The MuJoCo function that fills the struct with default values (e.g., mj_defaultOption).
+        /// When not given, the struct is zero-initialized.
+        #[arg(long)]
+        default_fn: Option<String>
+    },
+
+    /// Creates serde's Serialize/Deserialize implementations for a MuJoCo struct (e.g., mjOption_),
+    /// gated behind a cargo feature, along with round-trip tests.
+    CreateSerdeImpls {
+        /// Path to the documentation structs.h file containing the documentation-public structs.
+        structs_filepath: PathBuf,
+        /// The name of the struct for which to create the implementations.
+        struct_name: String,
+        /// Paths to headers defining the enums (e.g., mjmodel.h, mjvisualize.h), used to (de)serialize
+        /// enum fields by name. Enum fields of unknown enums are (de)serialized as numbers.
+        #[arg(long, num_args=0..)]
+        enum_headers: Vec<PathBuf>,
+        /// The cargo feature (of the output crate) that enables the implementations.
+        #[arg(long, default_value = "serde")]
+        feature: String,
+        /// The MuJoCo function that fills the struct with default values, used in the generated tests.
+        #[arg(long)]
+        default_fn: Option<String>
+    },
+
+    /// Creates compile-time checks of field types and ordering of structs against the bindgen bindings.
+    CreateLayoutChecks {
+        /// Paths to the headers containing the structs (e.g., structs.h, mjmodel.h).
+        #[arg(num_args=0..)]
+        header_paths: Vec<PathBuf>,
+        /// Names of the structs to check (e.g., mjModel_). When not given, all structs are checked.
+        #[arg(long, num_args=0..)]
+        structs: Vec<String>
+    },
+
+    /// Creates safe wrappers of the model editing API (mjspec.h): accessors of the element fields
+    /// (converting mjString and the numeric vectors) and the mjs_add*/mjs_find*/mjs_delete functions as methods.
+    CreateSpecWrappers {
+        /// Path to the mjspec.h file.
+        mjspec_h_path: PathBuf,
+        /// Names of the elements to wrap (e.g., mjsBody_). When not given, all elements are wrapped.
+        #[arg(long, num_args=0..)]
+        structs: Vec<String>
+    },
+
+    /// Reports which MJAPI functions, mjModel/mjData fields and view groups a mujoco-rs checkout
+    /// wraps, only declares in the raw bindings or misses entirely, with percentages per header section.
+    Coverage {
+        /// Path to the mujoco-rs checkout, whose Rust sources are scanned.
+        checkout: PathBuf,
+        /// Paths to MuJoCo's headers (e.g., mujoco.h, mjmodel.h, mjdata.h).
+        #[arg(num_args=0..)]
+        header_paths: Vec<PathBuf>,
+        /// Path to the indexer_xmacro.h file, defining the view groups.
+        #[arg(long)]
+        indexer_xmacro: Option<PathBuf>
+    }
+}
+
+
+/// Reads the file or exits the process, if the file can't be read.
+fn read_source(path: &Path) -> Source {
+    Source::read(path).unwrap_or_else(|error| {
+        eprintln!("error: could not read {}: {error}", path.display());
+        process::exit(1);
+    })
+}
+
+
+/// Reads and parses the output template or exits the process, if the template is invalid.
+fn read_template(path: Option<&Path>) -> Option<Template> {
+    let source = read_source(path?);
+    Some(Template::parse(&source).unwrap_or_else(|diagnostic| {
+        eprintln!("{diagnostic}");
+        process::exit(1);
+    }))
+}
+
+
+/// Parses all declarations of the header or, for a Python file (e.g., functions.py),
+/// of MuJoCo's introspect metadata.
+fn parse_header(path: &Path, diagnostics: &mut Vec<Diagnostic>) -> Ir {
+    let source = read_source(path);
+    if introspect::is_introspect(path) { introspect::parse_introspect(&source, diagnostics) } else { parse::parse_header(&source, diagnostics) }
+}
+
+/// Parses the functions of the header or the introspect metadata (functions.py).
+fn parse_functions(path: &Path, diagnostics: &mut Vec<Diagnostic>) -> Vec<Function> {
+    let source = read_source(path);
+    if introspect::is_introspect(path) { introspect::parse_introspect(&source, diagnostics).functions } else { parse::parse_functions(&source, diagnostics) }
+}
+
+/// Parses the structs of the header or the introspect metadata (structs.py).
+fn parse_structs(path: &Path, diagnostics: &mut Vec<Diagnostic>) -> Vec<Struct> {
+    let source = read_source(path);
+    if introspect::is_introspect(path) { introspect::parse_introspect(&source, diagnostics).structs } else { parse::parse_structs(&source, diagnostics) }
+}
+
+/// Parses the enums of the header or the introspect metadata (enums.py).
+fn parse_enums(path: &Path, diagnostics: &mut Vec<Diagnostic>) -> Vec<Enum> {
+    let source = read_source(path);
+    if introspect::is_introspect(path) { introspect::parse_introspect(&source, diagnostics).enums } else { parse::parse_enums(&source, diagnostics) }
+}
+
+
+/// Locates the files of `paths` given only by name in the MuJoCo tree `root`, replacing them with the found paths.
+/// Exits the process with an error listing all the files that could not be found.
+fn locate<'a>(root: Option<&MujocoRoot>, paths: impl IntoIterator<Item = &'a mut PathBuf>) {
+    let Some(root) = root else { return };
+    let mut paths: Vec<_> = paths.into_iter().filter(|path| path.components().count() == 1).collect();
+    let names: Vec<_> = paths.iter().map(|path| path.to_string_lossy().into_owned()).collect();
+    match root.locate_all(names.iter().map(String::as_str)) {
+        Ok(found) => paths.iter_mut().zip(found).for_each(|(path, found)| **path = found),
+        Err(error) => {
+            eprintln!("error: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+
+/// Returns the `header_paths` or, when none are given, all the headers of the MuJoCo tree `root`.
+/// Exits the process if there are no headers.
+fn header_paths_or_all(header_paths: Vec<PathBuf>, root: Option<&MujocoRoot>) -> Vec<PathBuf> {
+    if !header_paths.is_empty() {
+        return header_paths;
+    }
+    let Some(root) = root else {
+        eprintln!("error: no header paths were given (give them or use --mujoco-root)");
+        process::exit(1);
+    };
+    root.headers().unwrap_or_else(|error| {
+        eprintln!("error: {error}");
+        process::exit(1);
+    })
+}
+
+
+/// Reports the diagnostics and runs the generator on the IR, writing to stdout or into the --write file.
+/// With --dry-run, the diff of the file is printed instead of writing it.
+/// With a report format, the skipped declarations are reported to stderr afterwards.
+fn run(generator: impl Generator, ir: &Ir, diagnostics: &[Diagnostic], output: &Output) {
+    for diagnostic in diagnostics {
+        eprintln!("{diagnostic}\n");
+    }
+
+    let mut report = Report::default();
+    let mut code = vec![];
+    if let Err(error) = generator.generate_with_report(ir, &mut code, &mut report) {
+        eprintln!("error: {error}");
+        process::exit(1);
+    }
+    let code = String::from_utf8(code).expect("generated code is not valid UTF-8");
+
+    match &output.write {
+        Some(path) => write_output(path, &code, output),
+        None => print!("{code}"),
+    }
+
+    match output.report_format {
+        Some(ReportFormat::Text) => eprintln!("{report}"),
+        Some(ReportFormat::Json) => eprintln!("{}", report.to_json()),
+        None => {}
+    }
+}
+
+
+/// Writes the generated code into the file (or its region) or, with --dry-run, prints the diff and summary of the changes.
+/// A file that doesn't exist yet is created, unless a region is given.
+fn write_output(path: &Path, code: &str, output: &Output) {
+    let fail = |message: String| -> ! {
+        eprintln!("error: {message}");
+        process::exit(1);
+    };
+    let old = match std::fs::read_to_string(path) {
+        Ok(old) => old,
+        Err(error) if error.kind() == io::ErrorKind::NotFound && output.region.is_none() => String::new(),
+        Err(error) => fail(format!("could not read {}: {error}", path.display())),
+    };
+    let replaced = region::replace_region(&old, output.region.as_deref(), code)
+        .unwrap_or_else(|error| fail(format!("{}: {error}", path.display())));
+
+    if output.dry_run {
+        let label = match &output.region {
+            Some(region) => format!("{} [{region}]", path.display()),
+            None => path.display().to_string(),
+        };
+        let (old_label, new_label) = (format!("{label} (current)"), format!("{label} (regenerated)"));
+        print!("{}", diff::unified_diff(&old, &replaced.text, &old_label, &new_label, output.color));
+        println!("{}", diff::Summary::new(label, &replaced.old_content, &replaced.new_content, output.color));
+    }
+    else if replaced.text != old {
+        std::fs::write(path, &replaced.text).unwrap_or_else(|error| fail(format!("could not write {}: {error}", path.display())));
     }
 }
 
 
 fn main() {
     let parser = Args::parse(); 
+    let color = match parser.color {
+        ColorChoice::Auto => io::stdout().is_terminal(),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+    let output = Output {
+        report_format: parser.skipped_report, write: parser.write, region: parser.region, dry_run: parser.dry_run, color
+    };
+    let root = parser.mujoco_root.map(MujocoRoot::new);
+    let root = root.as_ref();
+    let mut diagnostics = vec![];
     use Command::*;
     match parser.command {
-        CreateViews { indexer_xmacro_path } => {
-            info_method::create_views(&indexer_xmacro_path);
+        CreateViews { mut indexer_xmacro_path, template, typed_ids, mut model_header } => {
+            locate(root, [&mut indexer_xmacro_path].into_iter().chain(model_header.as_mut()));
+            let template = read_template(template.as_deref());
+            let view_groups = parse::parse_view_groups(&read_source(&indexer_xmacro_path), &mut diagnostics);
+            let structs = match &model_header {
+                Some(model_header) => parse_structs(model_header, &mut diagnostics),
+                None => vec![],
+            };
+            run(info_method::ViewsGenerator { template, typed_ids }, &Ir { view_groups, structs, ..Default::default() }, &diagnostics, &output);
+        },
+
+        CreateRangeAccessors { mut indexer_xmacro_path, mut headers, typed_ids } => {
+            locate(root, [&mut indexer_xmacro_path].into_iter().chain(&mut headers));
+            let view_groups = parse::parse_view_groups(&read_source(&indexer_xmacro_path), &mut diagnostics);
+            let structs = headers.iter().flat_map(|header| parse_structs(header, &mut diagnostics)).collect();
+            run(range::RangeGenerator { typed_ids }, &Ir { view_groups, structs, ..Default::default() }, &diagnostics, &output);
+        },
+
+        CreateIdTypes => {
+            run(object_id::IdTypesGenerator, &Ir::default(), &diagnostics, &output);
         },
 
-        CreateFixedArrayFunctionWrappers { mujoco_h_path } => {
-            fixed_arr_fn::create_fixed_array_fn_wrappers(&mujoco_h_path);
+        CreateNameLookups { mut indexer_xmacro_path, mut model_header } => {
+            locate(root, [&mut indexer_xmacro_path].into_iter().chain(model_header.as_mut()));
+            let view_groups = parse::parse_view_groups(&read_source(&indexer_xmacro_path), &mut diagnostics);
+            let structs = match &model_header {
+                Some(model_header) => parse_structs(model_header, &mut diagnostics),
+                None => vec![],
+            };
+            run(name_lookup::NameLookupGenerator, &Ir { view_groups, structs, ..Default::default() }, &diagnostics, &output);
         },
 
-        CreateModelMethods { mujoco_h_path , struct_, blacklist} => {
-            model_fn::create_mj_self_methods(&mujoco_h_path, &struct_, &blacklist);
+        CreateFixedArrayFunctionWrappers { mut mujoco_h_path, with_tests, linalg } => {
+            locate(root, [&mut mujoco_h_path]);
+            let functions = parse_functions(&mujoco_h_path, &mut diagnostics);
+            let generator = fixed_arr_fn::FixedArrayFnGenerator { with_tests, linalg: linalg.into_iter().map(Into::into).collect() };
+            run(generator, &Ir { functions, ..Default::default() }, &diagnostics, &output);
+        },
+
+        CreateModelMethods { mut mujoco_h_path , struct_, blacklist, test_instance, test_model } => {
+            locate(root, [&mut mujoco_h_path]);
+            let functions = parse_functions(&mujoco_h_path, &mut diagnostics);
+            let generator = model_fn::SelfMethodsGenerator { self_name: struct_, blacklist, test_instance, test_model };
+            run(generator, &Ir { functions, ..Default::default() }, &diagnostics, &output);
+        }
+
+        CreateDerivativeWrappers { mut mujoco_h_path, shapes } => {
+            locate(root, [&mut mujoco_h_path]);
+            let functions = parse_functions(&mujoco_h_path, &mut diagnostics);
+            let shape_overrides = match shapes {
+                Some(shapes) => derivative::parse_shape_overrides(&read_source(&shapes), &mut diagnostics),
+                None => Default::default(),
+            };
+            run(derivative::DerivativeGenerator { shape_overrides }, &Ir { functions, ..Default::default() }, &diagnostics, &output);
+        }
+
+        CreateCallbacks { mut header_paths } => {
+            locate(root, &mut header_paths);
+            let header_paths = header_paths_or_all(header_paths, root);
+            let mut ir = Ir::default();
+            for header_path in &header_paths {
+                ir.extend(parse_header(header_path, &mut diagnostics));
+            }
+            run(callback::CallbackGenerator, &ir, &diagnostics, &output);
+        }
+
+        CreateTypes { mut api_reference, prefix } => {
+            locate(root, [&mut api_reference]);
+            let type_docs = parse::parse_type_docs(&read_source(&api_reference));
+            run(typedef::TypedefGenerator { prefix }, &Ir { type_docs, ..Default::default() }, &diagnostics, &output);
+        }
+
+        CreateArraySliceMacroCall { mut structs_filepath, accessor_prefix, struct_name, view_types, typed_ids, template } => {
+            locate(root, [&mut structs_filepath]);
+            let template = read_template(template.as_deref());
+            let structs = parse_structs(&structs_filepath, &mut diagnostics);
+            let generator = array_slice::ArraySliceGenerator { struct_name, accessor_prefix, view_types, typed_ids, template };
+            run(generator, &Ir { structs, ..Default::default() }, &diagnostics, &output);
+        }
+
+        CreateSparseViews { mut structs_filepath, accessor_prefix, struct_name, view_types } => {
+            locate(root, [&mut structs_filepath]);
+            let structs = parse_structs(&structs_filepath, &mut diagnostics);
+            let generator = sparse::SparseViewGenerator { struct_name, accessor_prefix, view_types };
+            run(generator, &Ir { structs, ..Default::default() }, &diagnostics, &output);
+        }
+
+        CreateGettersSetters { mut structs_filepath, struct_name, mut xml_reference, xml_element, overrides, error_type, template } => {
+            locate(root, [&mut structs_filepath].into_iter().chain(xml_reference.as_mut()));
+            let template = read_template(template.as_deref());
+            let structs = parse_structs(&structs_filepath, &mut diagnostics);
+            let metadata = constraints::collect_metadata(
+                xml_reference.as_deref().map(read_source).as_ref(), &xml_element,
+                overrides.as_deref().map(read_source).as_ref(), &mut diagnostics
+            );
+            let generator = getter_setter::GetterSetterGenerator { struct_name, metadata, error_type, template };
+            run(generator, &Ir { structs, ..Default::default() }, &diagnostics, &output);
+        }
+
+        CreateBuilder { mut structs_filepath, struct_name, default_fn } => {
+            locate(root, [&mut structs_filepath]);
+            let structs = parse_structs(&structs_filepath, &mut diagnostics);
+            run(builder::BuilderGenerator { struct_name, default_fn }, &Ir { structs, ..Default::default() }, &diagnostics, &output);
+        }
+
+        CreateSerdeImpls { mut structs_filepath, struct_name, mut enum_headers, feature, default_fn } => {
+            locate(root, [&mut structs_filepath].into_iter().chain(&mut enum_headers));
+            let structs = parse_structs(&structs_filepath, &mut diagnostics);
+            let mut enums = vec![];
+            for enum_header in &enum_headers {
+                enums.extend(parse_enums(enum_header, &mut diagnostics));
+            }
+            let generator = serde_impl::SerdeGenerator { struct_name, feature, default_fn };
+            run(generator, &Ir { structs, enums, ..Default::default() }, &diagnostics, &output);
         }
 
-        CreateTypes { api_reference, prefix } => {
-            typedef::create_types(&api_reference, prefix.as_deref());
+        CreateLayoutChecks { mut header_paths, structs } => {
+            locate(root, &mut header_paths);
+            let header_paths = header_paths_or_all(header_paths, root);
+            let mut ir = Ir::default();
+            for header_path in &header_paths {
+                ir.structs.extend(parse_structs(header_path, &mut diagnostics));
+            }
+            run(layout_check::LayoutCheckGenerator { struct_names: structs }, &ir, &diagnostics, &output);
         }
 
-        CreateArraySliceMacroCall { structs_filepath, accessor_prefix, struct_name } => {
-            array_slice::create_array_slice(&structs_filepath, &accessor_prefix, &struct_name);
+        CreateSpecWrappers { mut mjspec_h_path, structs } => {
+            locate(root, [&mut mjspec_h_path]);
+            let ir = parse_header(&mjspec_h_path, &mut diagnostics);
+            run(spec::SpecGenerator { struct_names: structs }, &ir, &diagnostics, &output);
         }
 
-        CreateGettersSetters { structs_filepath, struct_name } => {
-            getter_setter::create_getters_setters(&structs_filepath, &struct_name);
+        Coverage { checkout, mut header_paths, mut indexer_xmacro } => {
+            locate(root, header_paths.iter_mut().chain(indexer_xmacro.as_mut()));
+            let header_paths = header_paths_or_all(header_paths, root);
+            if let (Some(root), None) = (root, &indexer_xmacro) {
+                indexer_xmacro = root.locate_all(["indexer_xmacro.h"]).ok().and_then(|found| found.into_iter().next());
+            }
+            let mut ir = Ir::default();
+            for header_path in &header_paths {
+                ir.extend(parse_header(header_path, &mut diagnostics));
+            }
+            if let Some(indexer_xmacro) = &indexer_xmacro {
+                ir.view_groups = parse::parse_view_groups(&read_source(indexer_xmacro), &mut diagnostics);
+            }
+            let usage = coverage::Usage::scan(&checkout).unwrap_or_else(|error| {
+                eprintln!("error: could not scan {}: {error}", checkout.display());
+                process::exit(1);
+            });
+            if usage.bindings.is_empty() {
+                eprintln!("warning: no bindings generated by bindgen were found, unwrapped items are reported as missing\n");
+            }
+            run(coverage::CoverageGenerator { usage }, &ir, &diagnostics, &output);
         }
     }
 }
@@ -1,93 +1,133 @@
 //! Module for creating Mjx methods
 use inflector::Inflector;
-use std::path::Path;
-use std::fs;
-use regex;
-
-
-
-pub fn create_mj_self_methods(path: &Path, self_name: &str, blacklist: &[String]) {
-    let re = regex::Regex::new(&format!(r"(?s)((?://[^\r\n]*?\r?\n)+?)\s*MJAPI\s+((?:const\s+)?[\w*]+)\s+(\w+)\s*\(([^)]*?{self_name}[^)]*?)\)\s*;")).unwrap();
-    let fdata = fs::read_to_string(path).unwrap();
-
-    let strip_matches: &[_] = &["mj_", "mjv_", "mjr_", "mjd_", "mju_"];
-    for capture in re.captures_iter(&fdata) {
-        let (docstring, return_type, fn_name, param_string) = (
-            capture.get(1).unwrap().as_str(),
-            capture.get(2).unwrap().as_str(),
-            capture.get(3).unwrap().as_str(),
-            capture.get(4).unwrap().as_str(),
-        );
-
-        let return_type_out = if return_type == "void" {
-            String::new()
-        }
-        else if return_type.starts_with("mj") {
-            format!(" -> {}", return_type.to_pascal_case())
-        }
-        else {
-            format!(" -> std::ffi::c_{return_type}")
-        };
+use std::io::Write;
+
+use crate::fixed_arr_fn::doc_comment;
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::{Ir, Param};
+use crate::layout_check::rust_ident;
+use crate::smoke_test::{self, SmokeTest};
+
+
+
+/// Generates method wrappers for functions that take the struct `self_name` (e.g., mjModel) as a parameter.
+pub struct SelfMethodsGenerator {
+    /// The struct name to create method wrappers.
+    pub self_name: String,
+    /// Ignore the methods that contain these types in the parameters.
+    pub blacklist: Vec<String>,
+    /// When given, also create a test module with a smoke test of each method,
+    /// called on the instance created by this expression.
+    pub test_instance: Option<String>,
+    /// Expression creating the model of the smoke tests, bound to `model` before the instance is created.
+    /// Methods taking an mjModel are only tested when given.
+    pub test_model: Option<String>,
+}
 
-        let comment_string = docstring.lines().map(|x|
-            x.replace("//", "///").replace("[", r"\[")
-                .replace("]", r"\]")
-            ).collect::<Vec<_>>().join("\n");
+impl Generator for SelfMethodsGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let self_name = self.self_name.as_str();
+        let strip_matches: &[_] = &["mj_", "mjv_", "mjr_", "mjd_", "mju_"];
+        let mut smoke_tests = Vec::new();
+        for function in ir.functions.iter().filter(|f| f.params.iter().any(|p| p.c_type.contains(self_name))) {
+            let (return_type, fn_name) = (function.return_type.as_str(), function.name.as_str());
+            let return_type_out = if return_type == "void" {
+                String::new()
+            }
+            else if return_type.starts_with("mj") {
+                format!(" -> {}", return_type.to_pascal_case())
+            }
+            else {
+                format!(" -> std::ffi::c_{return_type}")
+            };
 
+            let processed = process_arguments(&function.params, self_name, &self.blacklist, self.test_model.is_some());
+            let (params, param_names, test_arguments) = match processed {
+                Ok(arguments) => arguments,
+                Err(reason) => {
+                    report.skip("function", fn_name, reason);
+                    continue;
+                }
+            };
 
-        if let Some((params, param_names)) = process_arguments(param_string, self_name, blacklist) {
-            // println!("{fn_name}({param_string}); Parsed: {params:?} {param_names:?}");            
-            println!("
-{comment_string}
+            let method_name = strip_matches.iter().fold(fn_name, |acc, p| acc.trim_start_matches(p)).to_snake_case();
+            writeln!(out, "
+{}
 pub fn {}({}){return_type_out} {{
     unsafe {{ {fn_name}({}) }}
 }}",
-strip_matches.iter().fold(fn_name, |acc, p| acc.trim_start_matches(p)).to_snake_case(),
-params.join(", "), param_names.join(", "));
+doc_comment(function), method_name,
+params.join(", "), param_names.join(", "))?;
+
+            // Create a smoke test on the test instance, if all the other arguments can be constructed
+            let Some(test_instance) = &self.test_instance else { continue };
+            match test_arguments.into_iter().collect::<Result<Vec<_>, _>>() {
+                Ok(arguments) => {
+                    let mutability = if params.first().is_some_and(|p| p == "&mut self") { "mut " } else { "" };
+                    let result_binding = if return_type_out.is_empty() { "" } else { "let _ = " };
+                    let model_binding = self.test_model.as_ref().map(|m| format!("let model = {m};\n")).unwrap_or_default();
+                    smoke_tests.push(SmokeTest {
+                        body: format!(
+                            "{model_binding}let {mutability}instance = {test_instance};\n{result_binding}instance.{method_name}({});",
+                            arguments.join(", ")
+                        ),
+                        name: method_name
+                    });
+                }
+                Err(reason) => report.skip("smoke test", method_name, reason),
+            }
+        }
+
+        if self.test_instance.is_some() {
+            smoke_test::write_smoke_tests(&smoke_tests, out)?;
         }
+        Ok(())
     }
 }
 
 
-fn process_arguments(param_string: &str, self_name: &str, blacklist: &[String]) -> Option<(Vec<String>, Vec<String>)> {
+/// Rust parameters, call arguments and smoke test arguments of a method.
+type ProcessedArguments = (Vec<String>, Vec<String>, Vec<Result<String, String>>);
+
+
+/// Converts the C parameters into Rust parameters, the corresponding call arguments and
+/// smoke test arguments (excluding self), which are the reason as an error when they can't be constructed.
+/// mjModel arguments are the test's `model`, available when `test_model` is set.
+/// Returns the reason as an error, when the parameters can't be converted.
+fn process_arguments(
+    params: &[Param], self_name: &str, blacklist: &[String], test_model: bool
+) -> Result<ProcessedArguments, String> {
     let mut out_parameters = Vec::new();
     let mut out_parameters_names = Vec::new();
-    let mut parameter_parts: Vec<_>;
-    let mut param_type;
-    let mut param_name;
-    let mut param_name_string;
+    let mut test_arguments = Vec::new();
     let mut mutability;
 
-    if blacklist.iter().any(|x| param_string.contains(x)) {
-        return None;
+    if let Some(param) = params.iter().find(|p| blacklist.iter().any(|x| p.c_type.contains(x.as_str()))) {
+        return Err(format!("parameter {} has a blacklisted type ({})", param.name, param.c_type));
     }
 
-    for parameter in param_string.split(",") {
-        parameter_parts = parameter.split_ascii_whitespace().collect();
-
-        let parameter_end = parameter_parts[parameter_parts.len() -1];
-        if parameter_end.ends_with("]") {  // is an array, thus a pointer from rust's level
-            /* Find the length part of the array */
-            let position_start = parameter_end.chars().position(|c| c == '[').unwrap();
-            let position_end = parameter_end.len() - 1;
-            let parameter_arr_len = &parameter_end[position_start + 1 .. position_end];
-
-            /* Parameter name */
-            let parameter_name = parameter_end[..position_start].to_snake_case();
-
+    for parameter in params {
+        let param_type = parameter.c_type.as_str();
+        let parameter_name = rust_ident(&parameter.name.to_snake_case());
+        if let Some(parameter_arr_len) = &parameter.array_len {  // is an array, thus a pointer from rust's level
             /* Obtain the reference operator and make the parameter call either .as_ptr() or .as_mut_ptr() */
-            (mutability, param_type) = if parameter_parts[0] == "const" {
+            mutability = if parameter.is_const {
                 out_parameters_names.push(format!("{parameter_name}.as_ptr()"));
-                ("&", parameter_parts[1])  // index 1 has the type
+                "&"
             }
             else {
                 out_parameters_names.push(format!("{parameter_name}.as_mut_ptr()"));
-                ("&mut ", parameter_parts[0])  // index 0 has the type
+                "&mut "
             };
-            
+
             if param_type.starts_with("void") {
-                return None;
+                return Err(format!("parameter {} is a void array", parameter.name));
             }
+            test_arguments.push(
+                smoke_test::argument_value(param_type, Some(parameter_arr_len), mutability != "&")
+                    .ok_or_else(|| format!("parameter {} can't be trivially constructed", parameter.name))
+            );
 
             if param_type.starts_with("mj") {
                 out_parameters.push(format!("{}: {mutability}[{}; {parameter_arr_len}]", parameter_name, param_type.to_pascal_case()));
@@ -96,41 +136,43 @@ fn process_arguments(param_string: &str, self_name: &str, blacklist: &[String])
                 out_parameters.push(format!("{}: {mutability}[std::ffi::c_{}; {parameter_arr_len}]", parameter_name, param_type));
             }
         }
+        else if parameter.is_const && param_type.starts_with(self_name) {
+            out_parameters_names.push("self.ffi()".into());
+            out_parameters.insert(0, "&self".into());
+        }
+        else if param_type.starts_with(self_name) {
+            out_parameters_names.push("self.ffi_mut()".into());
+            out_parameters.insert(0, "&mut self".into());
+        }
         else {
-            if parameter_parts[0] == "const" && parameter_parts[1].starts_with(self_name)  {
-                out_parameters_names.push("self.ffi()".into());
-                out_parameters.insert(0, "&self".into());
-            }
-            else if parameter_parts[0].starts_with(self_name) {
-                out_parameters_names.push("self.ffi_mut()".into());
-                out_parameters.insert(0, "&mut self".into());
+            mutability = if parameter.is_const { "&" } else { "&mut " };
+            let mut param_type_string = if param_type.starts_with("mj") {
+                param_type.to_pascal_case()
             }
             else {
-                (param_type, mutability) = if parameter_parts[0] == "const" {
-                    (parameter_parts[1], "&")
-                }
-                else {
-                    (parameter_parts[0], "&mut ")
-                };
-
-                param_name = parameter_parts[parameter_parts.len() - 1];
-                let is_pointer = param_type.ends_with("*") || param_name.starts_with("*");
-                let mut param_type_string = if param_type.starts_with("mj") {
-                    param_type.to_pascal_case()
-                }
-                else {
-                    format!("std::ffi::c_{}", param_type.trim_end_matches("*"))
-                };
-
-                if is_pointer {
-                    param_type_string = mutability.to_string() + &param_type_string;
-                }
+                format!("std::ffi::c_{}", param_type)
+            };
 
-                param_name_string = param_name.to_snake_case();  // also removes the pointer *
-                out_parameters.push(format!("{}: {}", param_name_string, param_type_string));
-                out_parameters_names.push(param_name_string);
+            if parameter.is_pointer() {
+                param_type_string = mutability.to_string() + &param_type_string;
+                test_arguments.push(match param_type {
+                    "mjModel" if !test_model => Err(format!("parameter {} needs the test model, which is not given", parameter.name)),
+                    // The test instance may borrow the model, e.g., MjData::new(&model)
+                    "mjModel" if !parameter.is_const => Err(format!("parameter {} needs a mutable model", parameter.name)),
+                    "mjModel" => Ok("&model".to_string()),
+                    _ => Err(format!("parameter {} may point to an array of unknown length", parameter.name)),
+                });
+            }
+            else {
+                test_arguments.push(
+                    smoke_test::argument_value(param_type, None, false)
+                        .ok_or_else(|| format!("parameter {} can't be trivially constructed", parameter.name))
+                );
             }
+
+            out_parameters.push(format!("{}: {}", parameter_name, param_type_string));
+            out_parameters_names.push(parameter_name);
         }
     }
-    Some((out_parameters, out_parameters_names))
+    Ok((out_parameters, out_parameters_names, test_arguments))
 }
@@ -0,0 +1,97 @@
+//! Module for generating name/id lookup helpers of MjModel for each object group (from indexer_xmacro.h).
+//! Names are read directly from the `names` buffer at the `name_*adr` addresses, instead of calling mj_name2id.
+use std::io::Write;
+
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::Ir;
+
+
+/// Groups whose mjtObj variant is not named after the group (e.g., MJMODEL_KEYFRAME is mjOBJ_KEY).
+const OBJECT_TYPE_EXCEPTIONS: [(&str, &str); 1] = [("KEYFRAME", "KEY")];
+
+
+/// Generates the `{group}_id` and `{group}_name` methods of MjModel for each MJMODEL group,
+/// along with the `object_id` and `object_name` methods taking the object type (mjtObj).
+/// When the IR contains mjModel_, groups without a name address array in it are skipped.
+pub struct NameLookupGenerator;
+
+impl Generator for NameLookupGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let model = ir.find_struct("mjModel_");
+        let mut methods = vec![];
+        let mut object_types = vec![];
+        for view_group in ir.view_groups.iter().filter(|g| g.class == "MODEL") {
+            let group_name = format!("MJ{}_{}", view_group.class, view_group.item);
+            // The prefix of the group's arrays (e.g., jnt of jnt_type) names the address array (e.g., name_jntadr)
+            let Some(entry) = view_group.entries.first() else {
+                report.skip("view group", group_name, "no entries");
+                continue;
+            };
+            let prefix = entry.prefix.trim().trim_end_matches('_');
+            let (address_array, count) = (format!("name_{prefix}adr"), entry.ntotaldim.as_str());
+            if model.is_some_and(|m| !m.fields.iter().any(|f| f.name == address_array)) {
+                report.skip("view group", group_name, format!("mjModel_ has no {address_array}"));
+                continue;
+            }
+
+            let item = view_group.item.to_lowercase();
+            let object_type = OBJECT_TYPE_EXCEPTIONS.iter().find(|(group, _)| *group == view_group.item)
+                .map_or(view_group.item.as_str(), |(_, object_type)| object_type);
+            methods.push(format!(
+"/// Returns the id of the {item} named `name` (mjOBJ_{object_type}), or [`None`] if there's no such {item}.
+pub fn {item}_id(&self, name: &str) -> Option<usize> {{
+    (0..self.ffi().{count} as usize).find(|id| self.{item}_name(*id) == Some(name))
+}}
+
+/// Returns the name of the {item} with the `id` (mjOBJ_{object_type}),
+/// or [`None`] if the id is out of range or the name is not valid UTF-8.
+pub fn {item}_name(&self, id: usize) -> Option<&str> {{
+    if id >= self.ffi().{count} as usize {{
+        return None;
+    }}
+    let address = unsafe {{ *self.ffi().{address_array}.add(id) }} as usize;
+    self.name_at(address)
+}}"));
+            object_types.push((object_type.to_string(), item));
+        }
+
+        if methods.is_empty() {
+            return Ok(());
+        }
+
+        let id_arms = object_types.iter().map(|(object_type, item)| format!("        MjtObj::mjOBJ_{object_type} => self.{item}_id(name),"));
+        let name_arms = object_types.iter().map(|(object_type, item)| format!("        MjtObj::mjOBJ_{object_type} => self.{item}_name(id),"));
+        writeln!(out, "{}\n", methods.join("\n\n"))?;
+        writeln!(out,
+"/// Returns the id of the object of `object_type` named `name`, or [`None`] if there's no such object
+/// or the object type has no names.
+pub fn object_id(&self, object_type: MjtObj, name: &str) -> Option<usize> {{
+    match object_type {{
+{}
+        _ => None,
+    }}
+}}
+
+/// Returns the name of the object of `object_type` with the `id`, or [`None`] if the id is out of range,
+/// the name is not valid UTF-8 or the object type has no names.
+pub fn object_name(&self, object_type: MjtObj, id: usize) -> Option<&str> {{
+    match object_type {{
+{}
+        _ => None,
+    }}
+}}
+
+/// Returns the NUL-terminated name starting at `address` of the `names` buffer.
+fn name_at(&self, address: usize) -> Option<&str> {{
+    if self.ffi().names.is_null() {{
+        return None;
+    }}
+    let names = unsafe {{ std::slice::from_raw_parts(self.ffi().names.cast::<u8>(), self.ffi().nnames as usize) }};
+    let name = names.get(address..)?;
+    let length = name.iter().position(|b| *b == 0)?;
+    std::str::from_utf8(&name[..length]).ok()
+}}", id_arms.collect::<Vec<_>>().join("\n"), name_arms.collect::<Vec<_>>().join("\n"))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+//! Module for typed ids of MuJoCo objects (e.g., `BodyId`), replacing the raw `int` ids of the fields,
+//! which reference other objects (e.g., `jnt_bodyid`). This way an id of a body can't index the geoms.
+//! The referenced object is inferred from the field's name and, as a fallback, from its comment.
+//!
+//! An id wraps the `int` (thus the arrays of ids can be cast) and returns its index as an [`Option`],
+//! which is [`None`] for -1 (no object).
+use std::io::Write;
+use std::sync::LazyLock;
+use regex::Regex;
+
+use crate::generator::{Error, Generator};
+use crate::ir::Ir;
+use crate::report::Report;
+
+
+/// Objects, which are referenced by id: the name of the id type, the name of the object
+/// and the abbreviations of the object in the field names (e.g., jnt_bodyid).
+const OBJECTS: [(&str, &str, &[&str]); 24] = [
+    ("BodyId", "body", &["body"]),
+    ("JointId", "joint", &["jnt", "joint"]),
+    ("DofId", "dof", &["dof"]),
+    ("GeomId", "geom", &["geom"]),
+    ("SiteId", "site", &["site"]),
+    ("CameraId", "camera", &["cam", "camera"]),
+    ("LightId", "light", &["light"]),
+    ("FlexId", "flex", &["flex"]),
+    ("MeshId", "mesh", &["mesh"]),
+    ("SkinId", "skin", &["skin"]),
+    ("HfieldId", "height field", &["hfield"]),
+    ("TextureId", "texture", &["tex", "texture"]),
+    ("MaterialId", "material", &["mat", "material"]),
+    ("PairId", "contact pair", &["pair"]),
+    ("ExcludeId", "excluded body pair", &["exclude"]),
+    ("EqualityId", "equality constraint", &["eq", "equality"]),
+    ("TendonId", "tendon", &["tendon"]),
+    ("ActuatorId", "actuator", &["actuator"]),
+    ("SensorId", "sensor", &["sensor"]),
+    ("NumericId", "numeric", &["numeric"]),
+    ("TextId", "text", &["text"]),
+    ("TupleId", "tuple", &["tuple"]),
+    ("KeyId", "keyframe", &["key"]),
+    ("PluginId", "plugin instance", &["plugin"]),
+];
+
+/// Names of ids (without the `id` suffix) referencing an object of the field's own type, e.g., body_parentid.
+const SAME_OBJECT: [&str; 3] = ["parent", "root", "weld"];
+
+/// Matches "id of body's parent" and "plugin instance id". Alternatives (e.g., "id of geom's mesh/hfield") are
+/// matched as a whole, thus no object is inferred.
+static COMMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bid of (\w+)'s ([\w/]+)|([\w/]+)(?: instance)? id\b").unwrap());
+
+
+/// Returns the id type and the name of the object abbreviated by `word` (e.g., jnt).
+pub fn find_object(word: &str) -> Option<(&'static str, &'static str)> {
+    OBJECTS.iter().find(|(_, _, abbreviations)| abbreviations.contains(&word)).map(|(id_type, object, _)| (*id_type, *object))
+}
+
+
+/// Returns the id type of the object abbreviated by `word` (e.g., jnt).
+pub fn id_type(word: &str) -> Option<&'static str> {
+    find_object(word).map(|(id_type, _)| id_type)
+}
+
+
+/// Returns the id type of the object, which the field `{prefix}{attribute}` references, or [`None`]
+/// if it's not an id or the object is unknown (e.g., sensor_objid, whose object type is in sensor_objtype).
+pub fn referenced_id_type(prefix: &str, attribute: &str, comment: &str) -> Option<&'static str> {
+    let own_type = || id_type(prefix.trim_end_matches('_'));
+
+    // The name is the object or ends with it (e.g., cam_targetbodyid)
+    if let Some(name) = attribute.strip_suffix("id").filter(|name| !name.is_empty()) {
+        if SAME_OBJECT.contains(&name) {
+            return own_type();
+        }
+        let object = OBJECTS.iter().flat_map(|(id_type, _, abbreviations)| abbreviations.iter().map(move |a| (*id_type, *a)))
+            .filter(|(_, abbreviation)| name.ends_with(abbreviation))
+            .max_by_key(|(_, abbreviation)| abbreviation.len());
+        if let Some((id_type, _)) = object {
+            return Some(id_type);
+        }
+    }
+
+    let capture = COMMENT_REGEX.captures(comment)?;
+    match (capture.get(1), capture.get(2), capture.get(3)) {
+        (Some(owner), Some(name), _) if SAME_OBJECT.contains(&name.as_str()) => id_type(owner.as_str()),
+        (_, Some(name), _) | (_, _, Some(name)) => id_type(name.as_str()),
+        _ => None,
+    }
+}
+
+
+/// The macro defining an id type.
+const ID_TYPE_MACRO: &str = r#"/// Defines the id of a MuJoCo object, which has the layout of MuJoCo's `int` ids.
+/// MuJoCo's -1 (no object) is `None` of the index.
+macro_rules! object_id {
+    ($name:ident, $object:literal) => {
+        #[doc = concat!("Id of a MuJoCo ", $object, " or of no object (-1).")]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name(i32);
+
+        impl $name {
+            /// The id of no object.
+            pub const NONE: Self = Self(-1);
+
+            /// Creates the id of the object at `index`.
+            pub fn new(index: usize) -> Self {
+                Self(i32::try_from(index).expect("index is out of range of MuJoCo ids"))
+            }
+
+            /// Returns the index into the arrays of the object, or `None` for the id of no object.
+            pub fn index(self) -> Option<usize> {
+                usize::try_from(self.0).ok()
+            }
+
+            /// Returns MuJoCo's id, which is -1 for no object.
+            pub fn raw(self) -> i32 {
+                self.0
+            }
+        }
+    };
+}
+"#;
+
+
+/// Generates the id types of all objects, which can be referenced by id.
+pub struct IdTypesGenerator;
+
+impl Generator for IdTypesGenerator {
+    fn generate_with_report(&self, _ir: &Ir, out: &mut dyn Write, _report: &mut Report) -> Result<(), Error> {
+        writeln!(out, "{ID_TYPE_MACRO}")?;
+        for (id_type, object, _) in OBJECTS {
+            writeln!(out, "object_id!({id_type}, \"{object}\");")?;
+        }
+        Ok(())
+    }
+}
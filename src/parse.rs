@@ -0,0 +1,351 @@
+//! Parsers of MuJoCo's headers and documentation into the [IR](crate::ir).
+//! Declarations that can't be parsed are skipped and reported as [diagnostics](crate::diagnostic).
+use std::sync::LazyLock;
+use regex::Regex;
+
+use crate::diagnostic::{Diagnostic, Source};
+use crate::ir::{Callback, CallbackType, Enum, EnumVariant, Field, Function, Ir, Param, Struct, TypeDoc, ViewEntry, ViewGroup};
+
+
+static FUNCTION_DECL_REGEX: LazyLock<Regex> = LazyLock::new(
+    || Regex::new(r"(?s)((?://[^\r\n]*\r?\n)*)\s*MJAPI\s+((?:const\s+)?[\w*]+)\s+(\w+)\s*\(([^)]*)\)\s*;").unwrap()
+);
+static FUNCTION_TYPE_REGEX: LazyLock<Regex> = LazyLock::new(
+    || Regex::new(r"(?s)((?://[^\r\n]*\r?\n)*)\s*typedef\s+((?:const\s+)?\w+\s*\**)\s*\(\s*\*\s*(\w+)\s*\)\s*\(([^)]*)\)\s*;").unwrap()
+);
+static CALLBACK_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"MJAPI\s+extern\s+(\w+)\s+(\w+)\s*;").unwrap());
+static INLINE_CALLBACK_REGEX: LazyLock<Regex> = LazyLock::new(
+    || Regex::new(r"MJAPI\s+extern\s+((?:const\s+)?\w+\s*\**)\s*\(\s*\*\s*(\w+)\s*\)\s*\(([^)]*)\)\s*;").unwrap()
+);
+static SECTION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^//-{3,}\s*(\w[^\r\n]*?)[\s-]*$").unwrap());
+static STRUCT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)(?:typedef\s+)?struct\s+(\w+)\s*\{(.*?)\n\}\s*\w*\s*;").unwrap());
+static FIELD_REGEX: LazyLock<Regex> = LazyLock::new(
+    || Regex::new(r"^((?:const\s+)?(?:unsigned\s+)?\w+)\s*(\**)\s*(\w+)\s*((?:\[[^\]]+\]\s*)*);$").unwrap()
+);
+static ARRAY_DIM_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]").unwrap());
+static ENUM_DEFINITION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)typedef\s+enum\s+\w+\s*\{(.*?)\}\s*(mjt\w+)\s*;").unwrap());
+static ENUM_VARIANT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\s*(mj\w+)\s*(?:=\s*([^,/\n]+?))?\s*,?\s*(?://.*)?$").unwrap());
+static VIEW_GROUP_REGEX: LazyLock<Regex> = LazyLock::new(
+    || regex::RegexBuilder::new(r"#define MJ(?<class>[A-z]+)_(?<item>[A-z]+).*?\)$")
+        .dot_matches_new_line(true)
+        .multi_line(true)
+        .build().unwrap()
+);
+static TYPE_DOC_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\w+[^~]*?)\.\.\s+mujoco-include::\s*([A-z]+)").unwrap());
+
+
+/// Parses everything a C header can contain: functions, structs and enums.
+pub fn parse_header(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Ir {
+    Ir {
+        functions: parse_functions(source, diagnostics),
+        structs: parse_structs(source, diagnostics),
+        enums: parse_enums(source, diagnostics),
+        function_types: parse_function_types(source, diagnostics),
+        callbacks: parse_callbacks(source, diagnostics),
+        ..Default::default()
+    }
+}
+
+
+/// Parses a parameter declaration (e.g., `const mjtNum vec[3]`).
+/// Returns [`None`] if the declaration has no type or no name.
+fn parse_param(declaration: &str) -> Option<Param> {
+    let (declaration, array_len) = match declaration.split_once('[') {
+        Some((declaration, len)) => (declaration, Some(len.trim_end_matches(']').trim().to_string())),
+        None => (declaration, None),
+    };
+
+    let pointer_depth = declaration.matches('*').count();
+    let mut words: Vec<_> = declaration.split(|c: char| c.is_whitespace() || c == '*').filter(|w| !w.is_empty()).collect();
+    let is_const = words.first() == Some(&"const");
+    if is_const {
+        words.remove(0);
+    }
+
+    let (name, c_type) = words.split_last()?;
+    if c_type.is_empty() {
+        return None;
+    }
+    Some(Param { name: name.to_string(), c_type: c_type.join(" "), is_const, pointer_depth, array_len })
+}
+
+
+/// Parses the comma separated parameter declarations of the function `name`.
+/// With `unnamed`, a declaration of only a type (e.g., `const char*` of a function pointer) is named `arg{n}`.
+/// Returns [`None`] and reports the parameter, if it can't be parsed.
+fn parse_params(
+    param_string: &str, name: &str, unnamed: bool, source: &Source, diagnostics: &mut Vec<Diagnostic>
+) -> Option<Vec<Param>> {
+    let mut params = vec![];
+    for (i, declaration) in param_string.split(',').map(str::trim).enumerate() {
+        if declaration.is_empty() || declaration == "void" {
+            continue;
+        }
+        let is_type_only = declaration.trim_start_matches("const").split(|c: char| c.is_whitespace() || c == '*')
+            .filter(|w| !w.is_empty()).count() == 1;
+        let param = if unnamed && is_type_only { parse_param(&format!("{declaration} arg{i}")) } else { parse_param(declaration) };
+        match param {
+            Some(param) => params.push(param),
+            None => {
+                diagnostics.push(source.warning(declaration, format!("could not parse a parameter of {name}, skipping the function")));
+                return None;
+            }
+        }
+    }
+    Some(params)
+}
+
+
+/// Converts the comment lines above a declaration into documentation lines, without the leading `//`.
+fn doc_lines(comment: &str) -> Vec<String> {
+    comment.lines().map(|line| line.trim_start().trim_start_matches("//").trim_end().to_string()).collect()
+}
+
+
+/// Parses functions declared with MJAPI, including the comment above the declaration.
+/// Functions with unparsable parameters and unrecognized MJAPI declarations are skipped.
+/// MJAPI variables (declared `extern`) are not functions and are skipped silently.
+pub fn parse_functions(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Vec<Function> {
+    let filedata = source.text.as_str();
+    let first_diagnostic = diagnostics.len();
+    let mut functions = vec![];
+    let mut declaration_spans = vec![];
+    let sections: Vec<_> = SECTION_REGEX.captures_iter(filedata).map(|c| (c.get(0).unwrap().start(), c.get(1).unwrap().as_str())).collect();
+    for capture in FUNCTION_DECL_REGEX.captures_iter(filedata) {
+        let (_, [comment, return_type, name, param_string]) = capture.extract();
+        declaration_spans.push(capture.get(0).unwrap().range());
+        let Some(params) = parse_params(param_string, name, false, source, diagnostics) else {
+            continue;
+        };
+
+        let doc = doc_lines(comment);
+        let position = capture.get(2).unwrap().start();
+        let section = sections.iter().rev().find(|(start, _)| *start < position).map_or("", |(_, title)| title);
+        functions.push(Function {
+            name: name.to_string(), return_type: return_type.to_string(), params, doc, section: section.to_string()
+        });
+    }
+
+    // Report MJAPI declarations, which were not recognized as functions
+    for (position, _) in filedata.match_indices("MJAPI") {
+        let line_start = filedata[..position].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = filedata[position..].find('\n').map_or(filedata.len(), |i| position + i);
+        let before = filedata[line_start..position].trim_start();
+        let after = &filedata[position + "MJAPI".len()..line_end];
+        if declaration_spans.iter().any(|span| span.contains(&position))
+            || before.starts_with('#') || before.contains("//") || after.trim_start().starts_with("extern")
+        {
+            continue;
+        }
+        diagnostics.push(source.warning(&filedata[position..line_end], "unrecognized MJAPI declaration, skipping it"));
+    }
+    diagnostics[first_diagnostic..].sort_by_key(|d| (d.line, d.column));
+    functions
+}
+
+
+/// Parses the function pointer types (e.g., `typedef void (*mjfGeneric)(const mjModel* m, mjData* d);`),
+/// including the comment above the declaration, as functions named after the type.
+/// Types with unparsable parameters are skipped.
+pub fn parse_function_types(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Vec<Function> {
+    FUNCTION_TYPE_REGEX.captures_iter(&source.text).filter_map(|capture| {
+        let (_, [comment, return_type, name, param_string]) = capture.extract();
+        let params = parse_params(param_string, name, true, source, diagnostics)?;
+        let return_type = return_type.split_whitespace().collect::<String>();
+        Some(Function { name: name.to_string(), return_type, params, doc: doc_lines(comment), section: String::new() })
+    }).collect()
+}
+
+
+/// Parses the global callbacks, i.e., function pointer variables declared with MJAPI extern, either of a
+/// function pointer type (e.g., `MJAPI extern mjfGeneric mjcb_control;`) or with an inline signature
+/// (e.g., `MJAPI extern void (*mju_user_error)(const char*);`). Other MJAPI variables are skipped silently.
+pub fn parse_callbacks(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Vec<Callback> {
+    let mut callbacks: Vec<_> = CALLBACK_REGEX.captures_iter(&source.text).map(|capture| {
+        let position = capture.get(0).unwrap().start();
+        (position, Callback { name: capture[2].to_string(), signature: CallbackType::Named(capture[1].to_string()) })
+    }).collect();
+    for capture in INLINE_CALLBACK_REGEX.captures_iter(&source.text) {
+        let (_, [return_type, name, param_string]) = capture.extract();
+        let Some(params) = parse_params(param_string, name, true, source, diagnostics) else {
+            continue;
+        };
+        let return_type = return_type.split_whitespace().collect::<String>();
+        let signature = Function { name: name.to_string(), return_type, params, doc: vec![], section: String::new() };
+        callbacks.push((capture.get(0).unwrap().start(), Callback { name: name.to_string(), signature: CallbackType::Inline(signature) }));
+    }
+    callbacks.sort_by_key(|(position, _)| *position);
+    callbacks.into_iter().map(|(_, callback)| callback).collect()
+}
+
+
+/// Parses all the structs and their fields.
+pub fn parse_structs(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Vec<Struct> {
+    STRUCT_REGEX.captures_iter(&source.text).map(|capture| {
+        let (_, [name, body]) = capture.extract();
+        let mut lines = body.lines().skip(1);  // skip the rest of the struct line
+        let (fields, _) = parse_fields(&mut lines, name, source, diagnostics);
+        Struct { name: name.to_string(), fields }
+    }).collect()
+}
+
+
+/// Parses fields of the struct body line by line, until the closing brace of the struct, whose
+/// trailing name (e.g., `} global;`) is returned along with the fields.
+/// Anonymous nested structs and unions are parsed recursively and named as bindgen names them,
+/// i.e., `{parent}__bindgen_ty_{n}` in the order of declaration.
+/// Unrecognized declarations are reported and skipped.
+fn parse_fields<'a>(
+    lines: &mut impl Iterator<Item = &'a str>, ffi_name: &str, source: &Source, diagnostics: &mut Vec<Diagnostic>
+) -> (Vec<Field>, &'a str) {
+    let mut fields = vec![];
+    let mut n_anonymous = 0;
+    let mut section = String::new();
+    while let Some(line) = lines.next() {
+        let (code, comment) = match line.split_once("//") {
+            Some((code, comment)) => (code.trim(), comment.trim()),
+            None => (line.trim(), ""),
+        };
+
+        // A standalone comment starts a new section (e.g., `// bodies` or `// ------- sizes`)
+        if code.is_empty() && !comment.trim_matches('-').trim().is_empty() {
+            section = comment.trim_matches('-').trim().to_string();
+            continue;
+        }
+
+        if (code.starts_with("struct") || code.starts_with("union")) && code.ends_with('{') {
+            let is_union = code.starts_with("union");
+            n_anonymous += 1;
+            let c_type = format!("{ffi_name}__bindgen_ty_{n_anonymous}");
+            let (nested, name) = parse_fields(lines, &c_type, source, diagnostics);
+            fields.push(Field {
+                name: name.to_string(), c_type, is_const: false, pointer_depth: 0, array_dims: vec![],
+                comment: comment.to_string(), nested: Some(nested), is_union, section: section.clone()
+            });
+        }
+        else if code.starts_with('}') {
+            let name = code.trim_start_matches('}').split(';').next().unwrap().trim();
+            return (fields, name);
+        }
+        else if let Some(capture) = FIELD_REGEX.captures(code) {
+            let (_, [c_type, pointers, name, dims]) = capture.extract();
+            let (is_const, c_type) = match c_type.strip_prefix("const") {
+                Some(c_type) => (true, c_type.trim()),
+                None => (false, c_type),
+            };
+            let array_dims: Vec<_> = ARRAY_DIM_REGEX.captures_iter(dims).map(|d| d[1].trim().to_string()).collect();
+            if array_dims.iter().any(String::is_empty) {
+                diagnostics.push(source.warning(dims, format!("array field {name} of {ffi_name} has an empty dimension, skipping it")));
+                continue;
+            }
+            fields.push(Field {
+                name: name.to_string(), c_type: c_type.split_whitespace().collect::<Vec<_>>().join(" "),
+                is_const, pointer_depth: pointers.len(), array_dims, comment: comment.to_string(), nested: None, is_union: false,
+                section: section.clone()
+            });
+        }
+        else if !code.is_empty() && !code.starts_with('#') {
+            diagnostics.push(source.warning(code, format!("unrecognized declaration in struct {ffi_name}, skipping it")));
+        }
+    }
+    (fields, "")
+}
+
+
+/// Evaluates the (simple) value expression of a variant: a decimal number or a left shift (e.g., 1<<3).
+/// Returns [`None`] when the shift overflows (e.g., 1<<64) or its amount is negative.
+fn evaluate_value(expression: &str) -> Option<i64> {
+    if let Some((left, right)) = expression.split_once("<<") {
+        let (left, shift) = (left.trim().parse::<i64>().ok()?, right.trim().parse::<u32>().ok()?);
+        // checked_shl only rejects shifts by the bit width or more, not the bits shifted out
+        left.checked_shl(shift).filter(|value| value >> shift == left)
+    }
+    else {
+        expression.trim().parse().ok()
+    }
+}
+
+
+/// Parses all enums defined with `typedef enum`.
+/// Variants without an explicit value take the value of the previous variant, incremented by one.
+/// Counting variants (starting with mjN, e.g., mjNDISABLE) are excluded.
+/// Variants with values that can't be evaluated are reported, and the enum is truncated before them.
+pub fn parse_enums(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Vec<Enum> {
+    let mut enums = vec![];
+    for capture in ENUM_DEFINITION_REGEX.captures_iter(&source.text) {
+        let (_, [body, enum_name]) = capture.extract();
+        let mut next_value = Some(0);
+        let mut variants = vec![];
+        for variant in ENUM_VARIANT_REGEX.captures_iter(body) {
+            let name = variant.get(1).unwrap().as_str();
+            let value = match variant.get(2) {
+                Some(expression) => match evaluate_value(expression.as_str()) {
+                    Some(value) => value,
+                    None => {
+                        diagnostics.push(source.warning(
+                            expression.as_str(), format!("could not evaluate the value of {name}, truncating {enum_name}")
+                        ));
+                        break;
+                    }
+                },
+                None => match next_value {
+                    Some(value) => value,
+                    None => {
+                        diagnostics.push(source.warning(name, format!("the value of {name} overflows, truncating {enum_name}")));
+                        break;
+                    }
+                },
+            };
+            next_value = value.checked_add(1);
+            if !name.starts_with("mjN") {
+                variants.push(EnumVariant { name: name.to_string(), value });
+            }
+        }
+        enums.push(Enum { name: enum_name.to_string(), variants });
+    }
+    enums
+}
+
+
+/// Parses the view groups, defined as X-macros in indexer_xmacro.h.
+/// Lines that are not `X(type, prefix, attribute, ntotaldim, dim)` entries are reported and skipped.
+pub fn parse_view_groups(source: &Source, diagnostics: &mut Vec<Diagnostic>) -> Vec<ViewGroup> {
+    VIEW_GROUP_REGEX.captures_iter(&source.text).map(|capture| {
+        let (class, item, capture_data) = (
+            capture.name("class").unwrap().as_str(),
+            capture.name("item").unwrap().as_str(),
+            capture.get(0).unwrap().as_str()
+        );
+
+        let mut entries = vec![];
+        for line in capture_data.lines().skip(1) {  // skip the #define line
+            let entry = line.trim().trim_end_matches('\\').trim_end();
+            // Remove parentheses. Split at last ), as multiple ) can appear due to MJ_M(...).
+            let parts = entry.split_once('(').and_then(|(_, e)| e.rsplit_once(')'))
+                .map(|(e, _)| e.split(',').map(str::trim).collect::<Vec<_>>());
+            match parts.as_deref() {
+                Some([_, _, _, _, dim]) if dim.strip_prefix("MJ_M(").is_some_and(|d| !d.contains(')')) => {
+                    diagnostics.push(source.warning(entry, format!("unclosed MJ_M( in the dimension of a MJ{class}_{item} entry, skipping it")));
+                }
+                Some([c_type, prefix, attribute, ntotaldim, dim]) => {
+                    entries.push(ViewEntry {
+                        c_type: c_type.to_string(), prefix: prefix.to_string(), attribute: attribute.to_string(),
+                        ntotaldim: ntotaldim.to_string(), dim: dim.to_string()
+                    });
+                }
+                _ if entry.is_empty() => {}
+                _ => diagnostics.push(source.warning(entry, format!("unrecognized entry of MJ{class}_{item}, skipping it"))),
+            }
+        }
+        ViewGroup { class: class.to_string(), item: item.to_string(), entries }
+    }).collect()
+}
+
+
+/// Parses the types documented in APItypes.rst, along with their documentation.
+pub fn parse_type_docs(source: &Source) -> Vec<TypeDoc> {
+    TYPE_DOC_REGEX.captures_iter(&source.text).map(|capture| {
+        let (_, [doc, name]) = capture.extract();
+        TypeDoc { name: name.trim_end_matches('_').to_string(), doc: doc.trim().to_string() }
+    }).collect()
+}
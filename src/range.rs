@@ -0,0 +1,208 @@
+//! Module for generating range accessors of MjModel from the address/count pairs, with which MuJoCo encodes
+//! the objects and array elements belonging to an object (e.g., body_jntadr and body_jntnum are the joints of a body).
+//! Addresses without a count (e.g., jnt_qposadr) end at the address of the next object.
+use std::io::Write;
+use std::sync::LazyLock;
+use inflector::Inflector;
+use regex::Regex;
+
+use crate::generator::{Error, Generator};
+use crate::getter_setter::convert_type;
+use crate::ir::{Ir, ViewGroup};
+use crate::object_id::{find_object, id_type};
+use crate::report::Report;
+
+
+/// Matches the length of a one-dimensional array at the end of a field comment, e.g., (nq x 1).
+static LENGTH_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\((\w+) x 1\)\s*$").unwrap());
+
+
+/// What the addresses of an address array point to.
+enum Target {
+    /// Objects (e.g., the joints of body_jntadr), with the id type, the name and the number of objects, if known.
+    Object { id_type: &'static str, name: &'static str, count: Option<String> },
+    /// Elements of an MjData array (e.g., act of actuator_actadr), with the Rust type and the length.
+    Data { name: String, rust_type: String, length: String },
+    /// Elements of an unknown array (e.g., the wrap objects of tendon_adr).
+    Unknown(String),
+}
+
+impl Target {
+    /// Returns the number of objects or elements, if known.
+    fn length(&self) -> Option<&str> {
+        match self {
+            Target::Object { count, .. } => count.as_deref(),
+            Target::Data { length, .. } => Some(length),
+            Target::Unknown(_) => None,
+        }
+    }
+
+    /// Returns the name used in the accessor names (e.g., joint of body_joint_range).
+    fn name(&self) -> String {
+        match self {
+            Target::Object { name, .. } => name.to_snake_case(),
+            Target::Data { name, .. } | Target::Unknown(name) => name.clone(),
+        }
+    }
+}
+
+
+/// Returns the prefix of the group's arrays (e.g., jnt of jnt_type).
+fn group_prefix(view_group: &ViewGroup) -> Option<&str> {
+    view_group.entries.first().map(|entry| entry.prefix.trim().trim_end_matches('_'))
+}
+
+
+/// Finds what the address array of `name` (e.g., jnt of body_jntadr) points to. Objects are counted
+/// by the MJMODEL groups, MjData arrays are found in the MJDATA groups or in mjData_ of the IR.
+fn find_target(ir: &Ir, name: &str) -> Target {
+    if let Some((id_type, object)) = find_object(name) {
+        let count = ir.view_groups.iter()
+            .filter(|g| g.class == "MODEL" && group_prefix(g).is_some_and(|prefix| find_object(prefix) == Some((id_type, object))))
+            .find_map(|g| g.entries.first())
+            .map(|entry| entry.ntotaldim.clone());
+        return Target::Object { id_type, name: object, count };
+    }
+
+    let entry = ir.view_groups.iter().filter(|g| g.class == "DATA").flat_map(|g| &g.entries)
+        .find(|entry| format!("{}{}", entry.prefix.trim(), entry.attribute) == name);
+    if let Some(entry) = entry {
+        return Target::Data { name: name.to_string(), rust_type: convert_type(&entry.c_type), length: entry.ntotaldim.clone() };
+    }
+
+    let field = ir.find_struct("mjData_").and_then(|s| s.flat_fields().into_iter().find(|f| f.name == name && f.is_pointer()));
+    if let Some(field) = field && let Some(capture) = LENGTH_REGEX.captures(&field.comment) {
+        return Target::Data { name: name.to_string(), rust_type: convert_type(&field.c_type), length: capture[1].to_string() };
+    }
+    Target::Unknown(name.to_string())
+}
+
+
+/// Generates the range accessors of MjModel for the address arrays of each MJMODEL group:
+/// - `{group}_{target}_range` returning the range of the addressed objects or elements,
+/// - `{group}_{targets}` returning the ids of the addressed objects (e.g., `body_joints`),
+/// - `{group}_{target}` and `{group}_{target}_mut` returning the sub-slice of the addressed MjData array
+///   (e.g., `actuator_act`), whose length is taken from the data's own model.
+///
+/// The address arrays are the `int` entries of the groups ending with `adr` and, when the IR contains mjModel_,
+/// its fields with the group's prefix (e.g., body_geomadr). The count is the array ending with `num` instead.
+/// Without one, the range ends at the address of the next object, if the length of the addressed array is known
+/// and the addresses can't be -1.
+#[derive(Default)]
+pub struct RangeGenerator {
+    /// Use the typed ids (see [`crate::object_id`]) for the ids of objects.
+    pub typed_ids: bool,
+}
+
+impl Generator for RangeGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let model = ir.find_struct("mjModel_");
+        let mut methods = vec![];
+        for view_group in ir.view_groups.iter().filter(|g| g.class == "MODEL") {
+            let Some(prefix) = group_prefix(view_group) else { continue };
+            let (item, count) = (view_group.item.to_lowercase(), view_group.entries[0].ntotaldim.as_str());
+
+            // The int arrays of the group, along with the ones of mjModel_
+            let mut arrays: Vec<String> = view_group.entries.iter()
+                .filter(|entry| entry.c_type == "int")
+                .map(|entry| format!("{}{}", entry.prefix.trim(), entry.attribute))
+                .collect();
+            for field in model.map(|m| m.flat_fields()).unwrap_or_default() {
+                if field.c_type == "int" && field.pointer_depth == 1 && field.name.starts_with(&format!("{prefix}_")) && !arrays.contains(&field.name) {
+                    arrays.push(field.name.clone());
+                }
+            }
+
+            for address_array in arrays.iter().filter(|name| name.ends_with("adr")) {
+                let base = address_array.trim_end_matches("adr");
+                let target = find_target(ir, base.strip_prefix(&format!("{prefix}_")).unwrap_or_default());
+                let target_name = target.name();
+                let range_name = if target_name.is_empty() { format!("{item}_range") } else { format!("{item}_{target_name}_range") };
+                let what = match &target {
+                    Target::Object { name, .. } => format!("the {}", name.to_plural()),
+                    Target::Data { name, .. } => format!("`{name}`"),
+                    Target::Unknown(_) => "the addressed elements".to_string(),
+                };
+
+                let count_array = format!("{base}num");
+                let range = if arrays.contains(&count_array) {
+                    format!(
+"/// Returns the range of {what} of the {item} `id` ({address_array} and {count_array}).
+/// Panics if the id is out of range.
+pub fn {range_name}(&self, id: usize) -> std::ops::Range<usize> {{
+    assert!(id < self.ffi().{count} as usize, \"{item} id out of range\");
+    let (address, count) = unsafe {{ (*self.ffi().{address_array}.add(id), *self.ffi().{count_array}.add(id)) }};
+    // The address is -1 when there's nothing to address
+    let start = address.max(0) as usize;
+    start..start + count as usize
+}}")
+                } else {
+                    let comment = model.and_then(|m| m.flat_fields().into_iter().find(|f| &f.name == address_array)).map(|f| f.comment.clone());
+                    if comment.is_some_and(|comment| comment.contains("-1")) {
+                        report.skip("field", format!("mjModel_.{address_array}"), format!("address can be -1 and there's no {count_array}"));
+                        continue;
+                    }
+                    let Some(length) = target.length() else {
+                        report.skip("field", format!("mjModel_.{address_array}"), format!("there's no {count_array} and the addressed length is unknown"));
+                        continue;
+                    };
+                    format!(
+"/// Returns the range of {what} of the {item} `id` ({address_array}), which ends at the address of the next {item}.
+/// Panics if the id is out of range.
+pub fn {range_name}(&self, id: usize) -> std::ops::Range<usize> {{
+    let count = self.ffi().{count} as usize;
+    assert!(id < count, \"{item} id out of range\");
+    let start = unsafe {{ *self.ffi().{address_array}.add(id) as usize }};
+    let end = if id + 1 < count {{ unsafe {{ *self.ffi().{address_array}.add(id + 1) as usize }} }} else {{ self.ffi().{length} as usize }};
+    start..end
+}}")
+                };
+                methods.push(range);
+
+                match &target {
+                    Target::Object { id_type: target_id_type, name, .. } => {
+                        let objects = name.to_snake_case().to_plural();
+                        let own_id_type = id_type(prefix).filter(|_| self.typed_ids);
+                        methods.push(match own_id_type {
+                            Some(own_id_type) => format!(
+"/// Returns the ids of the {} of the {item} `id`, which are none for no {item}.
+pub fn {item}_{objects}(&self, id: {own_id_type}) -> impl Iterator<Item = {target_id_type}> {{
+    id.index().map_or(0..0, |id| self.{range_name}(id)).map({target_id_type}::new)
+}}", name.to_plural()),
+                            None => format!(
+"/// Returns the ids of the {} of the {item} `id`. Panics if the id is out of range.
+pub fn {item}_{objects}(&self, id: usize) -> impl Iterator<Item = usize> {{
+    self.{range_name}(id)
+}}", name.to_plural()),
+                        });
+                    }
+                    Target::Data { name, rust_type, length } => {
+                        methods.push(format!(
+"/// Returns `{name}` of the {item} `id` in `data`. Panics if the id is out of range.
+/// The length of `{name}` is the one of data's own model, thus the range is checked against it.
+pub fn {item}_{name}<'a, M: Deref<Target = MjModel>>(&self, id: usize, data: &'a MjData<M>) -> &'a [{rust_type}] {{
+    let range = self.{range_name}(id);
+    let array = unsafe {{ std::slice::from_raw_parts(data.ffi().{name}, data.model().ffi().{length} as usize) }};
+    &array[range]
+}}
+
+/// Returns mutable `{name}` of the {item} `id` in `data`. Panics if the id is out of range.
+/// The length of `{name}` is the one of data's own model, thus the range is checked against it.
+pub fn {item}_{name}_mut<'a, M: Deref<Target = MjModel>>(&self, id: usize, data: &'a mut MjData<M>) -> &'a mut [{rust_type}] {{
+    let range = self.{range_name}(id);
+    let length = data.model().ffi().{length} as usize;
+    let array = unsafe {{ std::slice::from_raw_parts_mut(data.ffi_mut().{name}, length) }};
+    &mut array[range]
+}}"));
+                    }
+                    Target::Unknown(_) => {}
+                }
+            }
+        }
+
+        if !methods.is_empty() {
+            writeln!(out, "{}", methods.join("\n\n"))?;
+        }
+        Ok(())
+    }
+}
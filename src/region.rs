@@ -0,0 +1,84 @@
+//! Module for writing generated code into existing sources (e.g., of mujoco-rs), either replacing
+//! the whole file or a region delimited by marker comments:
+//! ```text
+//! // mujoco-rs-utils begin: views
+//! ...generated code...
+//! // mujoco-rs-utils end: views
+//! ```
+use std::fmt::{self, Display};
+
+
+/// Errors of locating a region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// There's no begin marker of the region.
+    Missing(String),
+    /// The begin marker of the region has no matching end marker.
+    Unclosed(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Missing(name) => write!(f, "there's no region `{name}` (marked with `{}`)", marker("begin", name)),
+            Error::Unclosed(name) => write!(f, "region `{name}` is not closed (with `{}`)", marker("end", name)),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+/// Returns the marker of the region `name` (without the comment prefix), e.g., `mujoco-rs-utils begin: views`.
+fn marker(kind: &str, name: &str) -> String {
+    format!("mujoco-rs-utils {kind}: {name}")
+}
+
+
+/// The text, whose region was replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replaced {
+    /// The whole new text.
+    pub text: String,
+    /// The previous content of the region.
+    pub old_content: String,
+    /// The new content of the region, i.e., the indented content.
+    pub new_content: String,
+}
+
+
+/// Returns the content with each non-empty line indented by `indent`, ending with a newline.
+fn indented(content: &str, indent: &str) -> String {
+    content.lines().map(|line| if line.is_empty() { "\n".to_string() } else { format!("{indent}{line}\n") }).collect()
+}
+
+
+/// Replaces the content of the region `name` of `text` with `content`, keeping the marker lines.
+/// The content is indented like the begin marker. Without a name, the whole text is replaced.
+pub fn replace_region(text: &str, name: Option<&str>, content: &str) -> Result<Replaced, Error> {
+    let Some(name) = name else {
+        let content = indented(content, "");
+        return Ok(Replaced { text: content.clone(), old_content: text.to_string(), new_content: content });
+    };
+
+    // The region is between the end of the begin marker line and the start of the end marker line
+    let is_marker = |line: &str, kind| line.trim_end().ends_with(&marker(kind, name));
+    let mut offset = 0;
+    let mut start = None;
+    for line in text.split_inclusive('\n') {
+        match start {
+            None if is_marker(line, "begin") => {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                start = Some((offset + line.len(), indented(content, indent)));
+            }
+            Some((start, new_content)) if is_marker(line, "end") => {
+                let old_content = text[start..offset].to_string();
+                let text = format!("{}{new_content}{}", &text[..start], &text[offset..]);
+                return Ok(Replaced { text, old_content, new_content });
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    Err(if start.is_some() { Error::Unclosed(name.to_string()) } else { Error::Missing(name.to_string()) })
+}
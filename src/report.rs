@@ -0,0 +1,64 @@
+//! Module for reporting the declarations, which the generators saw, but didn't generate code for.
+//! This shows the missing coverage, e.g., after upgrading MuJoCo.
+use std::fmt::{self, Display};
+
+
+/// A declaration, which was skipped by a generator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Skipped {
+    /// The kind of the declaration (e.g., function or field).
+    pub kind: &'static str,
+    /// The name of the declaration, fields are prefixed with their struct (e.g., mjModel_.key_mpos).
+    pub name: String,
+    pub reason: String,
+}
+
+
+/// Declarations skipped by a generator, in the order they were seen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub skipped: Vec<Skipped>,
+}
+
+impl Report {
+    /// Records a skipped declaration.
+    pub fn skip(&mut self, kind: &'static str, name: impl Into<String>, reason: impl Into<String>) {
+        self.skipped.push(Skipped { kind, name: name.into(), reason: reason.into() });
+    }
+
+    /// Returns the report as a JSON array of objects with the kind, name and reason.
+    pub fn to_json(&self) -> String {
+        let entries = self.skipped.iter().map(|Skipped { kind, name, reason }| format!(
+            "  {{\"kind\": \"{}\", \"name\": \"{}\", \"reason\": \"{}\"}}",
+            json_escape(kind), json_escape(name), json_escape(reason)
+        )).collect::<Vec<_>>();
+
+        if entries.is_empty() { "[]".to_string() } else { format!("[\n{}\n]", entries.join(",\n")) }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "skipped {} declaration(s)", self.skipped.len())?;
+        for Skipped { kind, name, reason } in &self.skipped {
+            write!(f, "\n  {kind} {name}: {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Escapes the string for use inside a JSON string literal.
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,222 @@
+//! Module for generating serde support of MuJoCo's parameter structs (e.g., mjOption, mjvCamera).
+use std::io::Write;
+use inflector::Inflector;
+
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::getter_setter::{convert_type, extract_possible_enum};
+use crate::ir::{Enum, Field, Ir};
+use crate::layout_check::{bindgen_field_type, bindgen_name};
+
+
+/// Helper module for (de)serializing fixed-size arrays as sequences.
+/// Unlike serde's implementation for arrays, this is not limited to 32 elements.
+const ARRAY_SEQ_MODULE: &str = r#"    /// (De)serializes fixed-size arrays as sequences.
+    mod array_seq {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+        pub fn serialize<S: Serializer, T: Serialize, const N: usize>(value: &[T; N], serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(value)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(deserializer: D) -> Result<[T; N], D::Error> {
+            let values = Vec::<T>::deserialize(deserializer)?;
+            let len = values.len();
+            values.try_into().map_err(|_| D::Error::invalid_length(len, &format!("an array of length {N}").as_str()))
+        }
+    }"#;
+
+/// Default value of the fields, which are not (de)serialized.
+const ZEROED_FN: &str = r#"    /// Returns the zero value of fields, which are not (de)serialized (e.g., pointers).
+    fn zeroed<T>() -> T {
+        // SAFETY: only used for fields of MuJoCo's C structs, i.e., numbers, arrays and pointers, which can be zero
+        unsafe { std::mem::zeroed() }
+    }"#;
+
+
+/// Creates a module for (de)serializing the `datatype` field, holding a value of `enum_`, by variant name.
+fn enum_module(enum_: &Enum, datatype: &str) -> String {
+    let (enum_type, variants) = (convert_type(&enum_.name), &enum_.variants);
+    let names = enum_.short_variant_names();
+    let pairs = names.iter().zip(variants).map(|(name, v)| format!("(\"{name}\", {})", v.value))
+        .collect::<Vec<_>>().join(", ");
+    let module_name = enum_type.to_snake_case();
+    format!(
+"    /// (De)serializes [`{enum_type}`] values by name.
+    mod {module_name} {{
+        use serde::{{Deserialize, Deserializer, Serializer, de, ser}};
+
+        const NAMES: [(&str, {datatype}); {}] = [{pairs}];
+
+        pub fn serialize<S: Serializer>(value: &{datatype}, serializer: S) -> Result<S::Ok, S::Error> {{
+            match NAMES.iter().find(|(_, v)| v == value) {{
+                Some((name, _)) => serializer.serialize_str(name),
+                None => Err(<S::Error as ser::Error>::custom(format!(\"invalid {enum_type} value: {{value}}\"))),
+            }}
+        }}
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<{datatype}, D::Error> {{
+            let name = String::deserialize(deserializer)?;
+            NAMES.iter().find(|(n, _)| *n == name).map(|(_, v)| *v).ok_or_else(|| {{
+                let expected = NAMES.map(|(n, _)| n).join(\", \");
+                <D::Error as de::Error>::custom(format!(\"unknown {enum_type} variant '{{name}}', expected one of: {{expected}}\"))
+            }})
+        }}
+    }}", variants.len())
+}
+
+
+/// Creates remote definitions (serde's `#[serde(remote = ...)]`) of the struct `remote` and its nested structs.
+/// Helper modules, needed by the definitions, are inserted into `modules`.
+/// Undocumented fields, pointers and multi-dimensional arrays of the struct `ffi_name`
+/// are not (de)serialized, but zeroed on deserialization, and recorded into `report`.
+fn remote_definitions(
+    fields: &[Field], ffi_name: &str, def_name: &str, remote: &str, ir: &Ir, modules: &mut Vec<String>, report: &mut Report
+) -> Vec<String> {
+    let mut attributes = vec![];
+    let mut definitions = vec![];
+    for field in fields {
+        let Field { name, c_type, comment, .. } = field;
+        let member = bindgen_name(name);
+        // The remote definition must list every field, thus skipped fields are zeroed
+        let mut skip = |reason: &str| {
+            report.skip("field", format!("{ffi_name}.{name}"), reason);
+            if !modules.iter().any(|m| m == ZEROED_FN) {
+                modules.push(ZEROED_FN.to_string());
+            }
+            attributes.push(format!("        #[serde(skip, default = \"zeroed\")]\n        {member}: {},", bindgen_field_type(field)));
+        };
+
+        if let Some(nested) = &field.nested {
+            let nested_def_name = format!("{}{}Def", def_name.trim_end_matches("Def"), name.to_pascal_case());
+            let nested_remote = format!("mujoco_c::{c_type}");
+            attributes.push(format!("        #[serde(with = \"{nested_def_name}\")]\n        {member}: {nested_remote},"));
+            definitions.extend(remote_definitions(nested, c_type, &nested_def_name, &nested_remote, ir, modules, report));
+        }
+        else if comment.is_empty() {
+            skip("undocumented");
+        }
+        else if field.is_pointer() {
+            skip("pointer");
+        }
+        else if let Some(size) = field.array_len() {
+            let (_, datatype, _) = extract_possible_enum(comment, c_type);
+            let type_cast = if size.chars().all(char::is_numeric) { "" } else { " as usize" };
+            attributes.push(format!("        #[serde(with = \"array_seq\")]\n        {member}: [{}; {size}{type_cast}],", convert_type(datatype)));
+        }
+        else if !field.array_dims.is_empty() {
+            skip("multi-dimensional array");
+        }
+        else {
+            let (_, enum_type, was_enum) = extract_possible_enum(comment, c_type);
+            let datatype = convert_type(c_type);
+            let with = match was_enum.then(|| ir.find_enum(enum_type)) {
+                Some(Some(enum_)) => {
+                    let module = enum_module(enum_, &datatype);
+                    if !modules.contains(&module) {
+                        modules.push(module);
+                    }
+                    format!("        #[serde(with = \"{}\")]\n", convert_type(enum_type).to_snake_case())
+                },
+                Some(None) => {
+                    report.skip("enum", enum_type, format!("not found, {ffi_name}.{name} is serialized as a number"));
+                    String::new()
+                }
+                None => String::new(),
+            };
+            let rename = if member != *name { format!("        #[serde(rename = \"{name}\")]\n") } else { String::new() };
+            attributes.push(format!("{with}{rename}        {member}: {datatype},"));
+        }
+    }
+
+    definitions.insert(0, format!(
+"    #[derive(Serialize, Deserialize)]
+    #[serde(remote = \"{remote}\")]
+    struct {def_name} {{
+{}
+    }}", attributes.join("\n")));
+    definitions
+}
+
+
+/// Generates serde's Serialize/Deserialize implementations for a MuJoCo struct (e.g., mjOption_),
+/// gated behind a cargo feature, along with a round-trip test.
+/// Enum fields are (de)serialized by variant name, when the enum is in the IR.
+pub struct SerdeGenerator {
+    /// The C name of the struct (e.g., mjOption_).
+    pub struct_name: String,
+    /// The cargo feature (of the output crate) that enables the implementations.
+    pub feature: String,
+    /// The MuJoCo function that fills the struct with default values, used in the generated test.
+    pub default_fn: Option<String>,
+}
+
+impl Generator for SerdeGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let struct_ = ir.find_struct(&self.struct_name).ok_or_else(|| Error::MissingStruct(self.struct_name.clone()))?;
+        create_serde_impls(&struct_.fields, &self.struct_name, ir, &self.feature, self.default_fn.as_deref(), out, report)
+    }
+}
+
+
+fn create_serde_impls(
+    fields: &[Field], struct_name: &str, ir: &Ir, feature: &str, default_fn: Option<&str>, out: &mut dyn Write,
+    report: &mut Report
+) -> Result<(), Error> {
+    let type_name = struct_name.trim_end_matches('_').to_pascal_case();
+    let module_name = type_name.to_snake_case();
+    let def_name = format!("{type_name}Def");
+
+    let mut modules = vec![ARRAY_SEQ_MODULE.to_string()];
+    let definitions = remote_definitions(fields, struct_name, &def_name, &type_name, ir, &mut modules, report);
+
+    writeln!(
+        out,
+"#[cfg(feature = \"{feature}\")]
+mod {module_name}_serde {{
+    use super::*;
+    use serde::{{Deserialize, Deserializer, Serialize, Serializer}};
+
+{}
+
+{}
+
+    impl Serialize for {type_name} {{
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {{
+            {def_name}::serialize(self, serializer)
+        }}
+    }}
+
+    impl<'de> Deserialize<'de> for {type_name} {{
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {{
+            {def_name}::deserialize(deserializer)
+        }}
+    }}
+}}", modules.join("\n\n"), definitions.join("\n\n"))?;
+
+    // Round-trip test, starting from MuJoCo's defaults if a default function is given.
+    let initial_value = if let Some(default_fn) = default_fn {
+        format!("let mut value = std::mem::MaybeUninit::uninit();
+        let value: {type_name} = unsafe {{
+            mujoco_c::{default_fn}(value.as_mut_ptr());
+            value.assume_init()
+        }};")
+    } else {
+        format!("let value: {type_name} = unsafe {{ std::mem::zeroed() }};")
+    };
+
+    writeln!(out, "
+#[cfg(all(test, feature = \"{feature}\"))]
+mod {module_name}_serde_tests {{
+    use super::*;
+
+    #[test]
+    fn {module_name}_json_round_trip() {{
+        {initial_value}
+        let json = serde_json::to_string(&value).unwrap();
+        let deserialized: {type_name} = serde_json::from_str(&json).unwrap();
+        assert_eq!(json, serde_json::to_string(&deserialized).unwrap());
+    }}
+}}")?;
+    Ok(())
+}
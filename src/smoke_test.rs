@@ -0,0 +1,64 @@
+//! Module for generating smoke tests, which call the generated function wrappers with trivial inputs.
+use std::io::{self, Write};
+
+
+/// A smoke test of a single wrapper.
+pub struct SmokeTest {
+    /// Name of the test function.
+    pub name: String,
+    /// The statements of the test body.
+    pub body: String,
+}
+
+
+/// Returns the zero literal of the C type or [`None`] if the type can't be trivially constructed.
+fn zero_literal(c_type: &str) -> Option<&'static str> {
+    match c_type {
+        "mjtNum" | "double" | "float" => Some("0.0"),
+        "int" | "char" | "unsigned" | "mjtByte" | "mjtSize" | "size_t" => Some("0"),
+        _ => None,
+    }
+}
+
+
+/// Creates an argument of the C type `c_type`. Arrays (`array_len` is given) are passed by (mutable) reference.
+/// Floating point arrays of length 4 and 9 are assumed to be quaternions and 3x3 matrices
+/// and are given as identity, all other values are zero.
+/// Returns [`None`] if the type can't be trivially constructed.
+pub fn argument_value(c_type: &str, array_len: Option<&str>, mutable: bool) -> Option<String> {
+    let zero = zero_literal(c_type)?;
+    let Some(array_len) = array_len else {
+        return Some(zero.to_string());
+    };
+
+    let reference = if mutable { "&mut " } else { "&" };
+    let value = match (zero, array_len) {
+        ("0.0", "4") => "[1.0, 0.0, 0.0, 0.0]".to_string(),
+        ("0.0", "9") => "[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]".to_string(),
+        _ if array_len.chars().all(char::is_numeric) => format!("[{zero}; {array_len}]"),
+        _ => format!("[{zero}; {array_len} as usize]"),
+    };
+    Some(format!("{reference}{value}"))
+}
+
+
+/// Writes a test module containing the `tests`. Nothing is written when there are no tests.
+pub fn write_smoke_tests(tests: &[SmokeTest], out: &mut dyn Write) -> io::Result<()> {
+    if tests.is_empty() {
+        return Ok(());
+    }
+
+    let tests = tests.iter().map(|SmokeTest { name, body }| format!(
+"    #[test]
+    fn smoke_{name}() {{
+        {}
+    }}", body.replace('\n', "\n        "))).collect::<Vec<_>>().join("\n\n");
+
+    writeln!(out, "
+#[cfg(test)]
+mod smoke_tests {{
+    use super::*;
+
+{tests}
+}}")
+}
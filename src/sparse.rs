@@ -0,0 +1,207 @@
+//! Module for generating views of the sparse matrices of mjData, which MuJoCo stores in the compressed sparse row
+//! (CSR) format: the values (e.g., efc_J) along with the `rownnz`, `rowadr` and `colind` arrays (e.g., efc_J_rownnz).
+//! The arrays are recognized by the naming convention, instead of exposing them as unrelated flat slices.
+use std::io::Write;
+use std::sync::LazyLock;
+use inflector::Inflector;
+use regex::Regex;
+
+use crate::generator::{Error, Generator};
+use crate::getter_setter::convert_type;
+use crate::ir::{Field, Ir, Struct};
+use crate::report::Report;
+
+
+/// Matches the dimensions at the end of a field comment, e.g., (nefc x nv).
+static DIMS_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\((\w+) x (\w+)\)\s*$").unwrap());
+
+/// Sparse matrices, whose values are not named after the structure arrays (e.g., D_rownnz of qDeriv).
+const VALUE_EXCEPTIONS: [(&str, &str); 1] = [("D", "qDeriv")];
+
+/// The number of columns when the values don't document it. MuJoCo's sparse matrices have a column for each dof.
+const DEFAULT_COLUMNS: &str = "nv";
+
+/// Matrices, which are only stored in the CSR format when the model is sparse (see mj_isSparse), otherwise dense.
+const SPARSE_MODEL_ONLY: [&str; 2] = ["efc_J", "ten_J"];
+
+/// The generic sparse view type, used by the sparse matrix methods.
+const SPARSE_VIEW_TYPE: &str = r#"/// An immutable view into a sparse matrix in the compressed sparse row (CSR) format: the non-zeros of row `i`
+/// are at `rowadr[i]..rowadr[i] + rownnz[i]` of `colind` (the columns) and `values`.
+#[derive(Debug)]
+pub struct MjSparseView<'a, T> {
+    values: &'a [T],
+    rownnz: &'a [i32],
+    rowadr: &'a [i32],
+    colind: &'a [i32],
+    ncols: usize,
+}
+
+impl<T> Clone for MjSparseView<'_, T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for MjSparseView<'_, T> {}
+
+impl<'a, T: Copy> MjSparseView<'a, T> {
+    /// Creates a new view. Panics if the non-zeros of a row are out of bounds of `colind` or `values`.
+    pub fn new(values: &'a [T], rownnz: &'a [i32], rowadr: &'a [i32], colind: &'a [i32], ncols: usize) -> Self {
+        assert_eq!(rownnz.len(), rowadr.len(), "rownnz and rowadr have different lengths");
+        let nnz_max = colind.len().min(values.len());
+        assert!(
+            rowadr.iter().zip(rownnz).all(|(&adr, &nnz)| adr >= 0 && nnz >= 0 && (adr + nnz) as usize <= nnz_max),
+            "non-zeros are out of bounds"
+        );
+        Self { values, rownnz, rowadr, colind, ncols }
+    }
+
+    /// Returns the number of rows.
+    pub fn nrows(&self) -> usize { self.rownnz.len() }
+
+    /// Returns the number of columns.
+    pub fn ncols(&self) -> usize { self.ncols }
+
+    /// Returns the number of stored non-zeros.
+    pub fn nnz(&self) -> usize { self.rownnz.iter().map(|&nnz| nnz as usize).sum() }
+
+    /// Returns the columns and values of the non-zeros of the `row`-th row.
+    pub fn row(&self, row: usize) -> (&'a [i32], &'a [T]) {
+        let (adr, nnz) = (self.rowadr[row] as usize, self.rownnz[row] as usize);
+        (&self.colind[adr..adr + nnz], &self.values[adr..adr + nnz])
+    }
+
+    /// Returns an iterator over the (column, value) non-zeros of the `row`-th row.
+    pub fn row_iter(&self, row: usize) -> impl Iterator<Item = (usize, T)> + use<'a, T> {
+        let (columns, values) = self.row(row);
+        columns.iter().zip(values).map(|(&column, &value)| (column as usize, value))
+    }
+
+    /// Returns an iterator over the rows, each being an iterator over its (column, value) non-zeros.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (usize, T)> + use<'a, T>> + use<'a, T> {
+        let view = *self;
+        (0..self.nrows()).map(move |row| view.row_iter(row))
+    }
+
+    /// Returns the value at (`row`, `column`) or [`None`] if it's not stored.
+    pub fn get(&self, row: usize, column: usize) -> Option<T> {
+        self.row_iter(row).find(|&(c, _)| c == column).map(|(_, value)| value)
+    }
+
+    /// Converts the matrix to a dense row-major matrix, whose entries that are not stored are zero
+    /// (e.g., the upper triangle of a symmetric matrix, whose lower triangle is stored).
+    pub fn to_dense(&self) -> Vec<T> where T: Default {
+        let mut dense = vec![T::default(); self.nrows() * self.ncols];
+        for row in 0..self.nrows() {
+            for (column, value) in self.row_iter(row) {
+                dense[row * self.ncols + column] = value;
+            }
+        }
+        dense
+    }
+}"#;
+
+
+/// Finds the values of the sparse matrix, whose structure arrays are prefixed by `base`: the array named `base`,
+/// or ending with it (e.g., actuator_moment of moment_rownnz), or an exception.
+fn find_values<'a>(struct_: &'a Struct, base: &str) -> Option<&'a Field> {
+    let exception = VALUE_EXCEPTIONS.iter().find(|(b, _)| *b == base).map(|(_, values)| *values);
+    let values: Vec<_> = struct_.flat_fields().into_iter().filter(|f| f.pointer_depth == 1 && f.c_type != "int").collect();
+    values.iter().find(|f| f.name == base || Some(f.name.as_str()) == exception)
+        .or_else(|| values.iter().find(|f| f.name.ends_with(&format!("_{base}"))))
+        .copied()
+}
+
+
+/// Generates a method returning the sparse view (`MjSparseView`) of each sparse matrix of a struct (e.g., mjData_),
+/// recognized by the `{base}_rownnz`, `{base}_rowadr` and `{base}_colind` arrays. The number of rows is the length
+/// of rownnz, the number of columns is the second dimension of the values, if documented, otherwise nv.
+/// The non-zeros are checked against the allocated lengths of the values and column indices, before reading them.
+pub struct SparseViewGenerator {
+    /// The C name of the struct (e.g., mjData_).
+    pub struct_name: String,
+    /// The prefix of the model sizes (e.g., model.ffi()). The sizes, which are fields of the struct, are read directly.
+    pub accessor_prefix: String,
+    /// Also create the generic sparse view type.
+    pub view_types: bool,
+}
+
+impl Generator for SparseViewGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let struct_ = ir.find_struct(&self.struct_name).ok_or_else(|| Error::MissingStruct(self.struct_name.clone()))?;
+        let fields = struct_.flat_fields();
+        let size = |name: &str| if fields.iter().any(|f| f.name == name && !f.is_pointer()) {
+            format!("self.ffi().{name}")
+        } else {
+            format!("{}.{name}", self.accessor_prefix)
+        };
+
+        let mut methods = vec![];
+        for rownnz in fields.iter().filter(|f| f.name.ends_with("_rownnz")) {
+            let base = rownnz.name.trim_end_matches("_rownnz");
+            let (rowadr, colind) = (format!("{base}_rowadr"), format!("{base}_colind"));
+            let name = format!("{}.{}", struct_.name, rownnz.name);
+            if let Some(missing) = [&rowadr, &colind].into_iter().find(|array| !fields.iter().any(|f| &&f.name == array)) {
+                report.skip("field", name, format!("there's no {missing}"));
+                continue;
+            }
+            let Some(values) = find_values(struct_, base) else {
+                report.skip("field", name, format!("there are no values of {base} (only the sparsity structure)"));
+                continue;
+            };
+            let Some(rows) = DIMS_REGEX.captures(&rownnz.comment).filter(|c| &c[2] == "1").map(|c| c[1].to_string()) else {
+                report.skip("field", name, "the number of rows is not documented");
+                continue;
+            };
+            let columns = DIMS_REGEX.captures(&values.comment).map(|c| c[2].to_string()).filter(|columns| columns != "1")
+                .unwrap_or(DEFAULT_COLUMNS.to_string());
+            // The allocated lengths bound the non-zeros, as the structure arrays are read before they are validated
+            let allocated = |field: &Field| DIMS_REGEX.captures(&field.comment).map(|c| match &c[2] {
+                "1" => format!("{} as usize", size(&c[1])),
+                columns => format!("{} as usize * {} as usize", size(&c[1]), size(columns)),
+            });
+            let colind_field = fields.iter().find(|f| f.name == colind).unwrap();
+            let (Some(values_len), Some(colind_len)) = (allocated(values), allocated(colind_field)) else {
+                report.skip("field", name, format!("the allocated length of {} or {colind} is not documented", values.name));
+                continue;
+            };
+            let allocated = if values_len == colind_len { values_len } else { format!("({values_len}).min({colind_len})") };
+
+            let (method, value_type) = (format!("{}_sparse", values.name.to_snake_case()), convert_type(&values.c_type));
+            let doc = DIMS_REGEX.replace(&values.comment, "").trim().to_string();
+            let (return_type, sparsity_check, view) = if SPARSE_MODEL_ONLY.contains(&values.name.as_str()) {
+                (
+                    format!("Option<MjSparseView<'_, {value_type}>>"),
+                    format!("\n    if unsafe {{ mujoco_c::mj_isSparse({}) }} == 0 {{\n        return None;\n    }}", self.accessor_prefix),
+                    "Some(MjSparseView::new(values, rownnz, rowadr, colind, ncols))",
+                )
+            } else {
+                (format!("MjSparseView<'_, {value_type}>"), String::new(), "MjSparseView::new(values, rownnz, rowadr, colind, ncols)")
+            };
+            let dense_doc = if sparsity_check.is_empty() { String::new() } else {
+                format!("\n/// Returns [`None`] when the model isn't sparse (see mj_isSparse), in which case `{}` is dense.", values.name)
+            };
+            methods.push(format!(
+"/// Returns the sparse view of `{values}`: {doc}.
+/// The structure is in {base}_rownnz, {rowadr} and {colind}.{dense_doc}
+/// Panics if the non-zeros of a row are out of bounds of the allocated `{values}` or {colind}.
+pub fn {method}(&self) -> {return_type} {{{sparsity_check}
+    let (nrows, ncols) = ({} as usize, {} as usize);
+    let rownnz = unsafe {{ std::slice::from_raw_parts(self.ffi().{base}_rownnz, nrows) }};
+    let rowadr = unsafe {{ std::slice::from_raw_parts(self.ffi().{rowadr}, nrows) }};
+    let allocated = {allocated};
+    let nnz = rowadr.iter().zip(rownnz).map(|(&adr, &nnz)| {{
+        assert!(adr >= 0 && nnz >= 0 && adr as usize + nnz as usize <= allocated, \"non-zeros are out of bounds\");
+        adr as usize + nnz as usize
+    }}).max().unwrap_or(0);
+    let colind = unsafe {{ std::slice::from_raw_parts(self.ffi().{colind}, nnz) }};
+    let values = unsafe {{ std::slice::from_raw_parts(self.ffi().{values}, nnz) }};
+    {view}
+}}", size(&rows), size(&columns), values = values.name));
+        }
+
+        writeln!(out, "{}", methods.join("\n\n"))?;
+        if self.view_types {
+            writeln!(out, "\n{SPARSE_VIEW_TYPE}")?;
+        }
+        Ok(())
+    }
+}
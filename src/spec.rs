@@ -0,0 +1,264 @@
+//! Module for generating safe wrappers of the model editing API (mjspec.h).
+//! The elements (e.g., mjsBody) are wrapped through their type aliases (e.g., MjsBody = mjsBody),
+//! with accessors of their fields and the mjs_add*, mjs_find* and mjs_delete functions as methods
+//! on the parent element or the spec.
+use std::io::Write;
+use inflector::Inflector;
+
+use crate::fixed_arr_fn::doc_comment;
+use crate::generator::{Error, Generator};
+use crate::getter_setter::convert_type;
+use crate::layout_check::{bindgen_name, rust_ident};
+use crate::report::Report;
+use crate::ir::{Field, Function, Ir, Param, Struct};
+
+
+/// Opaque vector types of the spec, which are converted through the `mjs_get*` and `mjs_set*` functions:
+/// (C type, Rust item type, getter, setter). Types without a getter in the C API are write-only.
+const VECTOR_TYPES: [(&str, &str, Option<&str>, &str); 4] = [
+    ("mjString", "str", Some("mjs_getString"), "mjs_setString"),
+    ("mjDoubleVec", "f64", Some("mjs_getDouble"), "mjs_setDouble"),
+    ("mjIntVec", "i32", None, "mjs_setInt"),
+    ("mjFloatVec", "f32", None, "mjs_setFloat"),
+];
+
+
+/// Generates wrappers of the spec elements `struct_names` (e.g., mjsBody_).
+/// When no names are given, all structs with an `element` field (of type mjsElement) are wrapped.
+pub struct SpecGenerator {
+    pub struct_names: Vec<String>,
+}
+
+impl Generator for SpecGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, report: &mut Report) -> Result<(), Error> {
+        let mut elements = vec![];
+        for struct_name in &self.struct_names {
+            elements.push(ir.find_struct(struct_name).ok_or_else(|| Error::MissingStruct(struct_name.clone()))?);
+        }
+        if self.struct_names.is_empty() {
+            elements.extend(ir.structs.iter().filter(|s| s.fields.iter().any(|f| f.name == "element" && f.c_type == "mjsElement")));
+        }
+
+        // Functions are methods of the type of their first parameter (e.g., mjs_addGeom of mjsBody)
+        let mut methods: Vec<(&str, Vec<String>)> = vec![];
+        for function in ir.functions.iter().filter(|f| f.name.starts_with("mjs_add") || f.name.starts_with("mjs_find") || f.name == "mjs_delete") {
+            let Some(parent) = function.params.first().filter(|p| p.pointer_depth == 1 && !p.is_const) else {
+                report.skip("function", &function.name, "first parameter is not a mutable pointer");
+                continue;
+            };
+            let method = match spec_method(function) {
+                Ok(method) => method,
+                Err(reason) => {
+                    report.skip("function", &function.name, reason);
+                    continue;
+                }
+            };
+            match methods.iter_mut().find(|(c_type, _)| *c_type == parent.c_type) {
+                Some((_, parent_methods)) => parent_methods.push(method),
+                None => methods.push((&parent.c_type, vec![method])),
+            }
+        }
+
+        for element in &elements {
+            let c_type = element.name.trim_end_matches('_');
+            let mut items = element_accessors(element, report);
+            if let Some(index) = methods.iter().position(|(parent, _)| *parent == c_type) {
+                items.extend(methods.remove(index).1);
+            }
+            writeln!(out, "impl {} {{\n{}\n}}\n", convert_type(c_type), items.join("\n\n"))?;
+        }
+
+        // Parents, which are not elements (e.g., mjSpec)
+        for (parent, parent_methods) in methods.into_iter().filter(|(parent, _)| !parent.starts_with("mjs")) {
+            writeln!(out, "impl {} {{\n{}\n}}\n", convert_type(parent), parent_methods.join("\n\n"))?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Creates the getters and setters of the fields of the element.
+/// Undocumented fields, multi-dimensional arrays and pointers other than the vector types are skipped.
+fn element_accessors(element: &Struct, report: &mut Report) -> Vec<String> {
+    let mut accessors = vec![];
+    for field in element.flat_fields() {
+        let Field { name, c_type, comment, .. } = field;
+        let (comment, member) = (comment.trim().trim_end_matches('.'), bindgen_name(name));
+        let method = rust_ident(name);
+        let skipped_name = format!("{}.{name}", element.name);
+        if name == "element" && c_type == "mjsElement" && field.pointer_depth == 1 {
+            accessors.push(
+"    /// Returns the element type of the element (e.g., for deleting it).
+    pub fn element(&mut self) -> &mut MjsElement {
+        unsafe { &mut *self.element }
+    }".to_string());
+        }
+        else if comment.is_empty() {
+            report.skip("field", skipped_name, "undocumented");
+        }
+        else if field.pointer_depth == 1 && let Some((_, item_type, getter, setter)) = VECTOR_TYPES.iter().find(|(t, ..)| t == c_type) {
+            accessors.extend(vector_accessors(&method, &member, comment, item_type, *getter, setter));
+            if getter.is_none() {
+                report.skip("getter", skipped_name, format!("{c_type} can't be read through the C API"));
+            }
+        }
+        else if field.is_pointer() {
+            report.skip("field", skipped_name, "pointer");
+        }
+        else if field.array_dims.len() > 1 {
+            report.skip("field", skipped_name, "multi-dimensional array");
+        }
+        else if let Some(size) = field.array_len() {
+            let datatype = convert_type(c_type);
+            let type_cast = if size.chars().all(char::is_numeric) { "" } else { " as usize" };
+            accessors.push(format!(
+"    /// Returns `{name}`: {comment}.
+    pub fn {method}(&self) -> &[{datatype}; {size}{type_cast}] {{
+        &self.{member}
+    }}
+
+    /// Sets `{name}`: {comment}.
+    pub fn set_{name}(&mut self, value: [{datatype}; {size}{type_cast}]) {{
+        self.{member} = value;
+    }}"));
+        }
+        else if c_type.starts_with("mj") && !c_type.starts_with("mjt") {  // nested struct (e.g., mjsOrientation)
+            let datatype = convert_type(c_type);
+            accessors.push(format!(
+"    /// Returns `{name}`: {comment}.
+    pub fn {method}(&self) -> &{datatype} {{
+        &self.{member}
+    }}
+
+    /// Returns `{name}` mutably: {comment}.
+    pub fn {name}_mut(&mut self) -> &mut {datatype} {{
+        &mut self.{member}
+    }}"));
+        }
+        else {
+            let datatype = convert_type(c_type);
+            accessors.push(format!(
+"    /// Returns `{name}`: {comment}.
+    pub fn {method}(&self) -> {datatype} {{
+        self.{member}
+    }}
+
+    /// Sets `{name}`: {comment}.
+    pub fn set_{name}(&mut self, value: {datatype}) {{
+        self.{member} = value;
+    }}"));
+        }
+    }
+    accessors
+}
+
+
+/// Creates the getter (if the C API has one) and the setter of a vector field.
+/// Strings are read as `String` and set from `&str`, numeric vectors are read as slices and set from slices.
+fn vector_accessors(method: &str, member: &str, comment: &str, item_type: &str, getter: Option<&str>, setter: &str) -> Vec<String> {
+    let name = method.trim_start_matches("r#");
+    let mut accessors = vec![];
+    match (item_type, getter) {
+        ("str", Some(getter)) => accessors.push(format!(
+"    /// Returns `{name}`: {comment}.
+    pub fn {method}(&self) -> String {{
+        unsafe {{ std::ffi::CStr::from_ptr(mujoco_c::{getter}(self.{member})) }}.to_string_lossy().into_owned()
+    }}")),
+        (_, Some(getter)) => accessors.push(format!(
+"    /// Returns `{name}`: {comment}.
+    pub fn {method}(&self) -> &[{item_type}] {{
+        let mut size = 0;
+        let data = unsafe {{ mujoco_c::{getter}(self.{member}, &mut size) }};
+        if data.is_null() {{ &[] }} else {{ unsafe {{ std::slice::from_raw_parts(data, size as usize) }} }}
+    }}")),
+        (_, None) => {}
+    }
+
+    accessors.push(if item_type == "str" { format!(
+"    /// Sets `{name}`: {comment}.
+    /// Returns an error when the value contains a NUL byte.
+    pub fn set_{name}(&mut self, value: &str) -> Result<(), std::ffi::NulError> {{
+        let value = std::ffi::CString::new(value)?;
+        unsafe {{ mujoco_c::{setter}(self.{member}, value.as_ptr()) }}
+        Ok(())
+    }}") } else { format!(
+"    /// Sets `{name}`: {comment}.
+    pub fn set_{name}(&mut self, value: &[{item_type}]) {{
+        unsafe {{ mujoco_c::{setter}(self.{member}, value.as_ptr(), value.len() as i32) }}
+    }}") });
+    accessors
+}
+
+
+/// Creates the method of an mjs_add*, mjs_find* or mjs_delete function on the type of its first parameter.
+/// Further pointer parameters of mjs_add* functions (e.g., the default class) are optional.
+/// Returns the reason, when the function can't be wrapped.
+fn spec_method(function: &Function) -> Result<String, String> {
+    let fn_name = function.name.as_str();
+    let optional_pointers = fn_name.starts_with("mjs_add");
+    let mut params = vec!["&mut self".to_string()];
+    let mut conversions = vec![];
+    let mut arguments = vec!["self".to_string()];
+    for param in &function.params[1..] {
+        let Param { name, c_type, is_const, pointer_depth, .. } = param;
+        let rust_name = bindgen_name(&name.to_snake_case());
+        let rust_type = convert_type(c_type);
+        let reference = if *is_const { "&" } else { "&mut " };
+        match (c_type.as_str(), pointer_depth) {
+            _ if param.array_len.is_some() => return Err(format!("parameter {name} is an array")),
+            ("char", 1) if *is_const => {
+                params.push(format!("{rust_name}: &str"));
+                conversions.push(format!("        let {rust_name} = std::ffi::CString::new({rust_name})?;"));
+                arguments.push(format!("{rust_name}.as_ptr()"));
+            }
+            (c_type, 1) if c_type.starts_with("mj") && !c_type.starts_with("mjt") && optional_pointers => {
+                let (null, pointer) = if *is_const { ("null", "*const _") } else { ("null_mut", "*mut _") };
+                params.push(format!("{rust_name}: Option<{reference}{rust_type}>"));
+                arguments.push(format!("{rust_name}.map_or(std::ptr::{null}(), |p| p as {pointer})"));
+            }
+            (c_type, 1) if c_type.starts_with("mj") && !c_type.starts_with("mjt") => {
+                params.push(format!("{rust_name}: {reference}{rust_type}"));
+                arguments.push(rust_name);
+            }
+            (_, 0) => {
+                params.push(format!("{rust_name}: {rust_type}"));
+                arguments.push(rust_name);
+            }
+            _ => return Err(format!("parameter {name} is an unsupported pointer")),
+        }
+    }
+
+    // Added elements are returned as references, found elements as optional references.
+    // Strings are converted to C strings, which fails on NUL bytes.
+    let return_type = function.return_type.as_str();
+    let call = format!("mujoco_c::{fn_name}({})", arguments.join(", "));
+    let (return_type, body) = match return_type.strip_suffix('*') {
+        Some(element) if element.starts_with("mjs") && fn_name.starts_with("mjs_find") => (
+            format!(" -> Option<&mut {}>", convert_type(element)),
+            format!("unsafe {{ {call}.as_mut() }}")
+        ),
+        Some(element) if element.starts_with("mjs") => (
+            format!(" -> &mut {}", convert_type(element)),
+            format!("unsafe {{ &mut *{call} }}")
+        ),
+        Some(_) => return Err("returns an unsupported pointer".to_string()),
+        None if return_type == "void" => (String::new(), format!("unsafe {{ {call} }}")),
+        None => (format!(" -> {}", convert_type(return_type)), format!("unsafe {{ {call} }}")),
+    };
+    let (return_type, body) = match (conversions.is_empty(), return_type.strip_prefix(" -> ")) {
+        (true, _) => (return_type, body),
+        (false, Some(return_type)) => (format!(" -> Result<{return_type}, std::ffi::NulError>"), format!("Ok({body})")),
+        (false, None) => (" -> Result<(), std::ffi::NulError>".to_string(), format!("{body};\n        Ok(())")),
+    };
+
+    let method_name = fn_name.trim_start_matches("mjs_").to_snake_case();
+    let mut doc = doc_comment(function).lines().map(|line| format!("    {line}")).collect::<Vec<_>>().join("\n");
+    if !conversions.is_empty() {
+        doc.push_str("\n    /// Returns an error when a string contains a NUL byte.");
+    }
+    let conversions = conversions.iter().map(|c| format!("{c}\n")).collect::<String>();
+    Ok(format!("{doc}
+    pub fn {method_name}({}){return_type} {{
+{conversions}        {body}
+    }}", params.join(", ")))
+}
@@ -0,0 +1,194 @@
+//! Module for the output templates of the generators, which write the macro calls of MuJoCo-rs
+//! (e.g., `info_method!` and `array_slice_dyn!` entries). The default templates (in templates/) contain
+//! the current formats; a template file given by the user replaces them.
+//! Only the macro calls are templated, the generators of plain Rust items (e.g., wrappers, builders and serde
+//! implementations) write them directly, as they don't depend on the macros of MuJoCo-rs.
+//!
+//! Templates use a subset of the Mustache syntax:
+//! - `{{name}}` is replaced by the value of the variable `name`,
+//! - `{{#name}}...{{/name}}` is repeated for each item of a list, or included if a flag is true or a text is not empty,
+//! - `{{^name}}...{{/name}}` is included if a flag is false, a text is empty or a list has no items,
+//! - `{{! comment }}` is removed.
+//!
+//! Inside a list, the variables of the item are looked up first, followed by the outer variables.
+//! Each item also has the flags `first` and `last` (e.g., for separators: `{{^last}}, {{/last}}`).
+//! Section and comment tags, which are alone on their line, remove the whole line.
+use crate::diagnostic::{Diagnostic, Severity, Source};
+use crate::generator::Error;
+
+
+/// A value of a template variable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Flag(bool),
+    List(Vec<Context>),
+}
+
+
+/// Variables available to a template.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Context {
+    variables: Vec<(&'static str, Value)>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the text variable `name`.
+    pub fn text(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.variables.push((name, Value::Text(value.into())));
+        self
+    }
+
+    /// Adds the flag `name`.
+    pub fn flag(mut self, name: &'static str, value: bool) -> Self {
+        self.variables.push((name, Value::Flag(value)));
+        self
+    }
+
+    /// Adds the list `name`, whose items are contexts of their own.
+    pub fn list(mut self, name: &'static str, items: Vec<Context>) -> Self {
+        self.variables.push((name, Value::List(items)));
+        self
+    }
+
+    fn get(&self, name: &str) -> Option<&Value> {
+        self.variables.iter().rev().find(|(n, _)| *n == name).map(|(_, v)| v)
+    }
+}
+
+
+/// A part of a parsed template.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Variable(String),
+    Section { name: String, inverted: bool, children: Vec<Node> },
+}
+
+
+/// A parsed template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    /// The name of the template, used in errors (e.g., the path of the template file).
+    pub name: String,
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    /// Parses the template from the source, failing on unclosed tags and unbalanced sections.
+    pub fn parse(source: &Source) -> Result<Template, Diagnostic> {
+        let text = source.text.as_str();
+        // The stack of open sections: (tag, name, inverted, nodes before the section)
+        let mut stack: Vec<(&str, String, bool, Vec<Node>)> = vec![];
+        let mut nodes = vec![];
+        let mut position = 0;
+        while let Some(start) = text[position..].find("{{").map(|i| position + i) {
+            let Some(end) = text[start..].find("}}").map(|i| start + i + 2) else {
+                return Err(source.diagnostic(Severity::Error, source.span_of(&text[start..start + 2]), "unclosed tag"));
+            };
+            let tag = &text[start..end];
+            let content = tag[2..tag.len() - 2].trim();
+            let (kind, name) = match content.chars().next() {
+                Some(c @ ('#' | '^' | '/' | '!')) => (Some(c), content[1..].trim()),
+                _ => (None, content),
+            };
+            if kind != Some('!') && (name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_')) {
+                return Err(source.diagnostic(Severity::Error, source.span_of(tag), "invalid variable name"));
+            }
+
+            // Section and comment tags alone on their line remove the line
+            let (mut text_end, mut next) = (start, end);
+            if kind.is_some() {
+                let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+                let line_end = text[end..].find('\n').map_or(text.len(), |i| end + i + 1);
+                if text[line_start..start].trim().is_empty() && text[end..line_end].trim().is_empty() {
+                    (text_end, next) = (line_start, line_end);
+                }
+            }
+            if position < text_end {
+                nodes.push(Node::Text(text[position..text_end].to_string()));
+            }
+            position = next;
+
+            match kind {
+                Some('#' | '^') => stack.push((tag, name.to_string(), kind == Some('^'), std::mem::take(&mut nodes))),
+                Some('/') => {
+                    let Some((_, open_name, inverted, before)) = stack.pop().filter(|(_, n, ..)| n == name) else {
+                        return Err(source.diagnostic(Severity::Error, source.span_of(tag), "closing tag without a matching section"));
+                    };
+                    let children = std::mem::replace(&mut nodes, before);
+                    nodes.push(Node::Section { name: open_name, inverted, children });
+                }
+                Some(_) => {}
+                None => nodes.push(Node::Variable(name.to_string())),
+            }
+        }
+        if let Some((tag, ..)) = stack.pop() {
+            return Err(source.diagnostic(Severity::Error, source.span_of(tag), "unclosed section"));
+        }
+        if position < text.len() {
+            nodes.push(Node::Text(text[position..].to_string()));
+        }
+        Ok(Template { name: source.path.display().to_string(), nodes })
+    }
+
+    /// Renders the template with the variables of `context`, failing on unknown variables.
+    pub fn render(&self, context: &Context) -> Result<String, String> {
+        let mut out = String::new();
+        render_nodes(&self.nodes, &[context], &mut out)
+            .map_err(|message| format!("{message} in the template {}", self.name))?;
+        Ok(out)
+    }
+}
+
+
+/// Renders the `template` or, when none is given, the default template `name` (in templates/) with the `text`.
+pub(crate) fn render_or_default(template: Option<&Template>, name: &str, text: &str, context: &Context) -> Result<String, Error> {
+    let rendered = match template {
+        Some(template) => template.render(context),
+        None => Template::parse(&Source::new(name, text)).expect("default template is invalid").render(context),
+    };
+    rendered.map_err(Error::Template)
+}
+
+
+fn render_nodes(nodes: &[Node], stack: &[&Context], out: &mut String) -> Result<(), String> {
+    fn lookup<'a>(stack: &[&'a Context], name: &str) -> Result<&'a Value, String> {
+        stack.iter().rev().find_map(|context| context.get(name)).ok_or_else(|| format!("unknown variable `{name}`"))
+    }
+
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Variable(name) => match lookup(stack, name)? {
+                Value::Text(text) => out.push_str(text),
+                Value::Flag(flag) => out.push_str(&flag.to_string()),
+                Value::List(_) => return Err(format!("list `{name}` used as a variable")),
+            },
+            Node::Section { name, inverted, children } => {
+                let value = lookup(stack, name)?;
+                let truthy = match value {
+                    Value::Text(text) => !text.is_empty(),
+                    Value::Flag(flag) => *flag,
+                    Value::List(items) => !items.is_empty(),
+                };
+                match value {
+                    Value::List(items) if !inverted => {
+                        for (i, item) in items.iter().enumerate() {
+                            let position = Context::new().flag("first", i == 0).flag("last", i + 1 == items.len());
+                            let item_stack: Vec<&Context> = stack.iter().copied().chain([&position, item]).collect();
+                            render_nodes(children, &item_stack, out)?;
+                        }
+                    }
+                    _ if truthy != *inverted => render_nodes(children, stack, out)?,
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
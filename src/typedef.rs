@@ -1,20 +1,28 @@
 //! Module for generating type definitions of existing types, to match PascalCase.
 
-use std::{fs, path::Path};
+use std::io::Write;
 use inflector::Inflector;
-use regex;
 
-pub fn create_types(api_reference: &Path, prefix: Option<&str>) {
-    let filedata = fs::read_to_string(api_reference).unwrap();
-    let struct_pat = prefix.unwrap_or("");
-    let re = regex::Regex::new(&format!(r"(\w+[^~]*?)\.\.\s+mujoco-include::\s*({struct_pat}[A-z]+)")).unwrap();
-    for captures in re.captures_iter(&filedata) {
-        let docstring = captures.get(1).unwrap().as_str().trim();
+use crate::generator::{Error, Generator};
+use crate::report::Report;
+use crate::ir::Ir;
 
-        let name = captures.get(2).unwrap().as_str().trim_end_matches("_");
-        for line in docstring.lines() {
-            println!("/// {line}");
+
+/// Generates PascalCase type definitions of the documented types (from APItypes.rst).
+pub struct TypedefGenerator {
+    /// The prefix that the type needs to have.
+    pub prefix: Option<String>,
+}
+
+impl Generator for TypedefGenerator {
+    fn generate_with_report(&self, ir: &Ir, out: &mut dyn Write, _report: &mut Report) -> Result<(), Error> {
+        let prefix = self.prefix.as_deref().unwrap_or("");
+        for type_doc in ir.type_docs.iter().filter(|t| t.name.starts_with(prefix)) {
+            for line in type_doc.doc.lines() {
+                writeln!(out, "/// {line}")?;
+            }
+            writeln!(out, "pub type {} = {};\n", type_doc.name.to_pascal_case(), type_doc.name)?;
         }
-        println!("pub type {} = {};\n", name.to_pascal_case(), name);
+        Ok(())
     }
 }
@@ -0,0 +1,25 @@
+{{! array_slice_dyn! entries of the pointer and array fields of a struct }}
+------------------------------------------------
+Processing lengths obtained via single attribute
+------------------------------------------------
+{{#single}}
+{{name}}: &[{{#item_size}}[{{type}}; {{item_size}}] [cast]{{/item_size}}{{^item_size}}{{type}}{{#cast}} [cast]{{/cast}}{{/item_size}}; "{{doc}}"; {{length}}],
+{{/single}}
+-------------------------------------------------------
+Processing multi-dimensional lengths with runtime shape
+-------------------------------------------------------
+{{#nd}}
+{{name}}: &[{{type}}{{#cast}} [cast]{{/cast}}; "{{doc}}"; [{{shape}}]],
+{{/nd}}
+--------------------------------------------------------
+Processing lengths obtained via sum of some length array
+--------------------------------------------------------
+{{#summed}}
+{{name}}: &[{{type}}; "{{doc}}"; [{{multiplier}}; ({{length}}); ({{accessor_prefix}}.)]],
+{{/summed}}
+{{#view_types}}
+-----------------------------------------
+Multi-dimensional view types (row-major)
+-----------------------------------------
+{{view_type_definitions}}
+{{/view_types}}
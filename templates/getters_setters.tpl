@@ -0,0 +1,13 @@
+{{! getter/setter/builder entries of the scalar and fixed-size array fields of a struct }}
+-----------------------------------------
+Processing normal getters/setters/builders
+-----------------------------------------
+{{#scalars}}
+{{name}}: {{#reference}}&{{/reference}}{{type}}; "{{doc}}.";{{#enum}} // force type coercion!{{/enum}}
+{{/scalars}}
+-----------------------------------------
+Processing array getters/setters/builders
+-----------------------------------------
+{{#arrays}}
+{{name}}: &[{{type}}; {{size}}]; "{{doc}}.";
+{{/arrays}}
@@ -0,0 +1,152 @@
+/// An immutable view into a row-major, multi-dimensional array with runtime shape.
+#[derive(Debug)]
+pub struct MjArrayView<'a, T, const D: usize> {
+    data: &'a [T],
+    shape: [usize; D],
+    strides: [usize; D],
+}
+
+impl<T, const D: usize> Clone for MjArrayView<'_, T, D> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T, const D: usize> Copy for MjArrayView<'_, T, D> {}
+
+/// A mutable view into a row-major, multi-dimensional array with runtime shape.
+#[derive(Debug)]
+pub struct MjArrayViewMut<'a, T, const D: usize> {
+    data: &'a mut [T],
+    shape: [usize; D],
+    strides: [usize; D],
+}
+
+/// Computes row-major strides of the given `shape`.
+const fn row_major_strides<const D: usize>(shape: [usize; D]) -> [usize; D] {
+    let mut strides = [1; D];
+    let mut i = D;
+    while i > 1 {
+        i -= 1;
+        strides[i - 1] = strides[i] * shape[i];
+    }
+    strides
+}
+
+/// Computes the flat index of `index`, or [`None`] when out of bounds.
+fn flat_index<const D: usize>(index: [usize; D], shape: &[usize; D], strides: &[usize; D]) -> Option<usize> {
+    let mut flat = 0;
+    for ((i, n), s) in index.into_iter().zip(shape).zip(strides) {
+        if i >= *n {
+            return None;
+        }
+        flat += i * s;
+    }
+    Some(flat)
+}
+
+impl<'a, T, const D: usize> MjArrayView<'a, T, D> {
+    /// Creates a new view. Panics if `data` is too short for `shape`.
+    pub fn new(data: &'a [T], shape: [usize; D]) -> Self {
+        assert!(data.len() >= shape.iter().product(), "data is too short for the given shape");
+        Self { data, shape, strides: row_major_strides(shape) }
+    }
+
+    /// Returns the shape (length of each dimension).
+    pub fn shape(&self) -> [usize; D] { self.shape }
+
+    /// Returns the number of elements to skip in the flat array when
+    /// incrementing the index of each dimension.
+    pub fn strides(&self) -> [usize; D] { self.strides }
+
+    /// Returns the underlying flat (row-major) data.
+    pub fn as_flat(&self) -> &'a [T] { &self.data[..self.shape.iter().product()] }
+
+    /// Returns a reference to the element at `index` or [`None`] if out of bounds.
+    pub fn get(&self, index: [usize; D]) -> Option<&'a T> {
+        flat_index(index, &self.shape, &self.strides).map(|i| &self.data[i])
+    }
+}
+
+impl<'a, T> MjArrayView<'a, T, 2> {
+    /// Returns the number of rows.
+    pub fn nrows(&self) -> usize { self.shape[0] }
+
+    /// Returns the number of columns.
+    pub fn ncols(&self) -> usize { self.shape[1] }
+
+    /// Returns the `row`-th row as a contiguous slice.
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(row < self.shape[0], "row index out of bounds");
+        &self.data[row * self.strides[0]..][..self.shape[1]]
+    }
+
+    /// Returns an iterator over the rows.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + use<'a, T> {
+        let view = *self;
+        (0..self.shape[0]).map(move |r| view.row(r))
+    }
+
+    /// Returns an iterator over the elements of the `col`-th column.
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        assert!(col < self.shape[1], "column index out of bounds");
+        // Without rows, there's no element of the column in the (empty) data
+        self.as_flat().get(col..).unwrap_or(&[]).iter().step_by(self.strides[0])
+    }
+}
+
+impl<'a, T> MjArrayView<'a, T, 3> {
+    /// Returns the `index`-th 2-D sub-matrix (along the first dimension).
+    pub fn matrix(&self, index: usize) -> MjArrayView<'a, T, 2> {
+        assert!(index < self.shape[0], "matrix index out of bounds");
+        MjArrayView::new(&self.data[index * self.strides[0]..][..self.strides[0]], [self.shape[1], self.shape[2]])
+    }
+}
+
+impl<'a, T, const D: usize> MjArrayViewMut<'a, T, D> {
+    /// Creates a new view. Panics if `data` is too short for `shape`.
+    pub fn new(data: &'a mut [T], shape: [usize; D]) -> Self {
+        assert!(data.len() >= shape.iter().product(), "data is too short for the given shape");
+        Self { data, shape, strides: row_major_strides(shape) }
+    }
+
+    /// Returns the shape (length of each dimension).
+    pub fn shape(&self) -> [usize; D] { self.shape }
+
+    /// Returns the number of elements to skip in the flat array when
+    /// incrementing the index of each dimension.
+    pub fn strides(&self) -> [usize; D] { self.strides }
+
+    /// Returns an immutable view of the same data.
+    pub fn as_view(&self) -> MjArrayView<'_, T, D> {
+        MjArrayView { data: self.data, shape: self.shape, strides: self.strides }
+    }
+
+    /// Returns a mutable reference to the element at `index` or [`None`] if out of bounds.
+    pub fn get_mut(&mut self, index: [usize; D]) -> Option<&mut T> {
+        flat_index(index, &self.shape, &self.strides).map(|i| &mut self.data[i])
+    }
+}
+
+impl<T> MjArrayViewMut<'_, T, 2> {
+    /// Returns the `row`-th row as a contiguous mutable slice.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.shape[0], "row index out of bounds");
+        &mut self.data[row * self.strides[0]..][..self.shape[1]]
+    }
+
+    /// Returns an iterator over mutable elements of the `col`-th column.
+    pub fn col_mut(&mut self, col: usize) -> impl Iterator<Item = &mut T> {
+        assert!(col < self.shape[1], "column index out of bounds");
+        let len = self.shape.iter().product();
+        // Without rows, there's no element of the column in the (empty) data
+        self.data[..len].get_mut(col..).unwrap_or(&mut []).iter_mut().step_by(self.strides[0])
+    }
+}
+
+impl<T> MjArrayViewMut<'_, T, 3> {
+    /// Returns the `index`-th 2-D sub-matrix (along the first dimension).
+    pub fn matrix_mut(&mut self, index: usize) -> MjArrayViewMut<'_, T, 2> {
+        assert!(index < self.shape[0], "matrix index out of bounds");
+        let stride = self.strides[0];
+        MjArrayViewMut::new(&mut self.data[index * stride..][..stride], [self.shape[1], self.shape[2]])
+    }
+}
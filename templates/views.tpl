@@ -0,0 +1,15 @@
+{{! info_method! and info_with_view! calls of each view group (from indexer_xmacro.h) }}
+{{#groups}}
+info_method! { {{class}}, ffi(), {{item}},
+    [{{fixed_lengths}}],
+    [{{external_lengths}}],
+    [{{dynamic_lengths}}]
+}
+
+{{/groups}}
+{{#groups}}
+info_with_view!({{class}}, {{item}},
+    [{{attributes}}],
+    []{{#data}}, M: Deref<Target = MjModel>{{/data}});
+
+{{/groups}}
@@ -0,0 +1,56 @@
+//! Tests of the build script helper, writing into a temporary output directory.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use mujoco_rs_utils::build_script::{BuildScript, Error};
+use mujoco_rs_utils::fixed_arr_fn::FixedArrayFnGenerator;
+
+
+fn include_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/mujoco-root/include")
+}
+
+
+#[test]
+fn run_into_out_dir() {
+    let out_dir = std::env::temp_dir().join(format!("mujoco-rs-utils-build-script-{}", std::process::id()));
+    fs::create_dir_all(&out_dir).unwrap();
+    let build_script = BuildScript::new(include_dir())
+        .out_dir(&out_dir)
+        .output("fixed_arr_fn.rs", FixedArrayFnGenerator::default());
+
+    let written = build_script.run().unwrap();
+    let path = out_dir.join("fixed_arr_fn.rs");
+    assert_eq!(written, std::slice::from_ref(&path));
+    let code = fs::read_to_string(&path).unwrap();
+    assert!(code.contains("mujoco_c::mju_add3("));
+
+    // Date back the file, so that rewriting it would update the modification time
+    let past = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    fs::File::options().write(true).open(&path).unwrap().set_modified(past).unwrap();
+    build_script.run().unwrap();
+    assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), past, "unchanged file was rewritten");
+
+    fs::write(&path, "outdated").unwrap();
+    build_script.run().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), code, "changed file was not rewritten");
+
+    fs::remove_dir_all(&out_dir).unwrap();
+}
+
+
+#[test]
+fn out_dir_from_env() {
+    let out_dir = std::env::temp_dir().join(format!("mujoco-rs-utils-build-script-env-{}", std::process::id()));
+    fs::create_dir_all(&out_dir).unwrap();
+    let build_script = BuildScript::new(include_dir()).output("fixed_arr_fn.rs", FixedArrayFnGenerator::default());
+
+    let written = build_script.run_with_env(|key| (key == "OUT_DIR").then(|| out_dir.clone().into())).unwrap();
+    assert_eq!(written, [out_dir.join("fixed_arr_fn.rs")]);
+
+    let result = build_script.run_with_env(|_| None);
+    assert!(matches!(result, Err(Error::MissingOutDir)));
+
+    fs::remove_dir_all(&out_dir).unwrap();
+}
@@ -0,0 +1,45 @@
+//---------------------------------- callback function types -------------------------------------
+
+// generic MuJoCo function
+typedef void (*mjfGeneric)(const mjModel* m, mjData* d);
+
+// contact filter: 1- discard, 0- collide
+typedef int (*mjfConFilt)(const mjModel* m, mjData* d, int geom1, int geom2);
+
+// sensor simulation
+typedef void (*mjfSensor)(const mjModel* m, mjData* d, int stage);
+
+// timer
+typedef mjtNum (*mjfTime)(void);
+
+// actuator dynamics, gain, bias
+typedef mjtNum (*mjfAct)(const mjModel* m, const mjData* d, int id);
+
+// collision detection
+typedef int (*mjfCollision)(const mjModel* m, const mjData* d,
+                            mjContact* con, int g1, int g2, mjtNum margin);
+
+//---------------------------------- Global variables --------------------------------------------
+
+// user error and memory handlers
+MJAPI extern void  (*mju_user_error)(const char*);
+MJAPI extern void  (*mju_user_warning)(const char*);
+MJAPI extern void* (*mju_user_malloc)(size_t);
+MJAPI extern void  (*mju_user_free)(void*);
+
+// callbacks extending computation pipeline
+MJAPI extern mjfGeneric  mjcb_passive;
+MJAPI extern mjfGeneric  mjcb_control;
+MJAPI extern mjfConFilt  mjcb_contactfilter;
+MJAPI extern mjfSensor   mjcb_sensor;
+MJAPI extern mjfTime     mjcb_time;
+MJAPI extern mjfAct      mjcb_act_dyn;
+MJAPI extern mjfAct      mjcb_act_gain;
+MJAPI extern mjfAct      mjcb_act_bias;
+MJAPI extern mjfUndeclared mjcb_undeclared;
+
+// collision function table
+MJAPI extern mjfCollision mjCOLLISIONFUNC[mjNGEOMTYPES][mjNGEOMTYPES];
+
+// string names
+MJAPI extern const char* mjDISABLESTRING[mjNDISABLE];
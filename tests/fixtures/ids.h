@@ -0,0 +1,24 @@
+struct mjModel_ {
+  // sizes needed at mjModel construction
+  int nbody;                      // number of bodies
+  int njnt;                       // number of joints
+  int ngeom;                      // number of geoms
+
+  // objects referencing other objects by id
+  int*      body_parentid;        // id of body's parent                      (nbody x 1)
+  int*      body_rootid;          // id of root above body                    (nbody x 1)
+  int*      body_mocapid;         // id of mocap data; -1: none               (nbody x 1)
+  int*      body_plugin;          // plugin instance id; -1: not in use       (nbody x 1)
+  int*      jnt_bodyid;           // id of joint's body                       (njnt x 1)
+  int*      dof_bodyid;           // id of dof's body                         (nv x 1)
+  int*      dof_jntid;            // id of dof's joint                        (nv x 1)
+  int*      dof_parentid;         // id of dof's parent; -1: none             (nv x 1)
+  int*      geom_bodyid;          // id of geom's body                        (ngeom x 1)
+  int*      geom_dataid;          // id of geom's mesh/hfield; -1: none       (ngeom x 1)
+  int*      geom_matid;           // material id for rendering; -1: none      (ngeom x 1)
+  int*      cam_targetbodyid;     // target body for tracking/targeting       (ncam x 1)
+  int*      actuator_trnid;       // transmission id: joint, tendon, site     (nu x 2)
+  int*      sensor_objtype;       // type of sensorized object (mjtObj)       (nsensor x 1)
+  int*      sensor_objid;         // id of sensorized object                  (nsensor x 1)
+  int*      tuple_objid;          // object id                                (ntupledata x 1)
+};
@@ -0,0 +1,29 @@
+#define MJMODEL_BODY                                            \
+  X( int,     body_, parentid,     nbody, 1                   ) \
+  X( int,     body_, rootid,       nbody, 1                   ) \
+  X( int,     body_, mocapid,      nbody, 1                   ) \
+  X( int,     body_, plugin,       nbody, 1                   ) \
+  X( mjtNum,  body_, pos,          nbody, 3                   )
+
+#define MJMODEL_JOINT                                           \
+  X( int,     jnt_,  type,         njnt,  1                   ) \
+  X( int,     jnt_,  bodyid,       njnt,  1                   )
+
+#define MJMODEL_DOF                                             \
+  X( int,     dof_,  bodyid,       nv,    1                   ) \
+  X( int,     dof_,  jntid,        nv,    1                   ) \
+  X( int,     dof_,  parentid,     nv,    1                   )
+
+#define MJMODEL_GEOM                                            \
+  X( int,     geom_, bodyid,       ngeom, 1                   ) \
+  X( int,     geom_, dataid,       ngeom, 1                   ) \
+  X( int,     geom_, matid,        ngeom, 1                   )
+
+#define MJMODEL_CAMERA                                          \
+  X( int,     cam_,  bodyid,       ncam,  1                   ) \
+  X( int,     cam_,  targetbodyid, ncam,  1                   )
+
+#define MJMODEL_SENSOR                                          \
+  X( int,     sensor_, objtype,    nsensor, 1                 ) \
+  X( int,     sensor_, objid,      nsensor, 1                 ) \
+  X( int,     sensor_, dim,        nsensor, 1                 )
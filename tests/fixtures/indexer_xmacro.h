@@ -0,0 +1,28 @@
+#define MJMODEL_BODY                                            \
+  X( int,     body_, parentid,     nbody, 1                   ) \
+  X( int,     body_, jntnum,       nbody, 1                   ) \
+  X( int,     body_, jntadr,       nbody, 1                   ) \
+  X( mjtNum,  body_, pos,          nbody, 3                   ) \
+  X( mjtNum,  body_, quat,         nbody, 4                   )
+
+#define MJMODEL_JOINT                                           \
+  X( int,     jnt_,  type,         njnt,  1                   ) \
+  X( int,     jnt_,  bodyid,       njnt,  1                   ) \
+  X( mjtByte, jnt_,  limited,      njnt,  1                   ) \
+  X( mjtNum,  jnt_,  qpos0,        njnt,  MJ_M(jnt_qposadr)   )
+
+#define MJMODEL_ACTUATOR                                        \
+  X( int,     actuator_, trntype,  nu,    1                   ) \
+  X( int,     actuator_, trnid,    nu,    2                   ) \
+  X( int,     actuator_, actadr,   nu,    1                   ) \
+  X( int,     actuator_, actnum,   nu,    1                   ) \
+  X( mjtNum,  actuator_, gear,     nu,    6                   )
+
+#define MJDATA_BODY                                             \
+  X( mjtNum,  x,     pos,          nbody, 3                   ) \
+  X( mjtNum,  x,     quat,         nbody, 4                   ) \
+  X( mjtNum,  ,      _cvel,        nbody, 6                   )
+
+#define MJDATA_ACTUATOR                                         \
+  X( mjtNum,  ,      ctrl,         nu,    1                   ) \
+  X( mjtNum,  ,      act,          na,    MJ_M(actuator_actnum) )
@@ -0,0 +1,46 @@
+# Copyright 2022 DeepMind Technologies Limited
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+# ==============================================================================
+"""Provides information about MuJoCo API enums.
+
+DO NOT EDIT. THIS FILE IS AUTOMATICALLY GENERATED.
+"""
+
+from typing import Mapping
+
+from .ast_nodes import EnumDecl
+
+ENUMS: Mapping[str, EnumDecl] = dict([
+    ('mjtIntegrator',
+     EnumDecl(
+         name='mjtIntegrator',
+         declname='enum mjtIntegrator_',
+         values=dict([
+             ('mjINT_EULER', 0),
+             ('mjINT_RK4', 1),
+             ('mjINT_IMPLICIT', 2),
+             ('mjINT_IMPLICITFAST', 3),
+         ]),
+     )),
+    ('mjtDisableBit',
+     EnumDecl(
+         name='mjtDisableBit',
+         declname='enum mjtDisableBit_',
+         values=dict([
+             ('mjDSBL_CONSTRAINT', 1),
+             ('mjDSBL_EQUALITY', 2),
+             ('mjNDISABLE', 2),
+         ]),
+     )),
+])
@@ -0,0 +1,499 @@
+# Copyright 2022 DeepMind Technologies Limited
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+# ==============================================================================
+"""Provides information about MuJoCo API functions.
+
+DO NOT EDIT. THIS FILE IS AUTOMATICALLY GENERATED.
+"""
+
+from typing import Mapping
+
+from .ast_nodes import ArrayType
+from .ast_nodes import FunctionDecl
+from .ast_nodes import FunctionParameterDecl
+from .ast_nodes import PointerType
+from .ast_nodes import ValueType
+
+FUNCTIONS: Mapping[str, FunctionDecl] = dict([
+    ('mju_add3',
+     FunctionDecl(
+         name='mju_add3',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='res',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(3,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='vec1',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(3,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='vec2',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(3,),
+                 ),
+             ),
+         ),
+         doc='Set res = vec1 + vec2.',
+     )),
+    ('mju_dot3',
+     FunctionDecl(
+         name='mju_dot3',
+         return_type=ValueType(name='mjtNum'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='vec1',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(3,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='vec2',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(3,),
+                 ),
+             ),
+         ),
+         doc='Return dot-product of vec1 and vec2.',
+     )),
+    ('mju_quat2Mat',
+     FunctionDecl(
+         name='mju_quat2Mat',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='res',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(9,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='quat',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(4,),
+                 ),
+             ),
+         ),
+         doc='Convert quaternion to 3D rotation matrix.',
+     )),
+    ('mju_mulQuat',
+     FunctionDecl(
+         name='mju_mulQuat',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='res',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(4,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='quat1',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(4,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='quat2',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(4,),
+                 ),
+             ),
+         ),
+         doc='Multiply quaternions.',
+     )),
+    ('mju_zero',
+     FunctionDecl(
+         name='mju_zero',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='res',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum'),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='n',
+                 type=ValueType(name='int'),
+             ),
+         ),
+         doc='Set res = 0.',
+     )),
+    ('mj_sizeModel',
+     FunctionDecl(
+         name='mj_sizeModel',
+         return_type=ValueType(name='int'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel', is_const=True),
+                 ),
+             ),
+         ),
+         doc='Return size of buffer needed to hold model.',
+     )),
+    ('mj_resetData',
+     FunctionDecl(
+         name='mj_resetData',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='d',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjData'),
+                 ),
+             ),
+         ),
+         doc='Reset data to defaults.',
+     )),
+    ('mj_name2id',
+     FunctionDecl(
+         name='mj_name2id',
+         return_type=ValueType(name='int'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='type',
+                 type=ValueType(name='int'),
+             ),
+             FunctionParameterDecl(
+                 name='name',
+                 type=PointerType(
+                     inner_type=ValueType(name='char', is_const=True),
+                 ),
+             ),
+         ),
+         doc='Get id of object with the specified mjtObj type and name, returns -1 if id not found.',  # pylint: disable=line-too-long
+     )),
+    ('mj_setConst',
+     FunctionDecl(
+         name='mj_setConst',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel'),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='d',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjData'),
+                 ),
+             ),
+         ),
+         doc='Set solver parameters to default values.',
+     )),
+    ('mj_differentiatePos',
+     FunctionDecl(
+         name='mj_differentiatePos',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='qvel',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum'),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='dt',
+                 type=ValueType(name='mjtNum'),
+             ),
+             FunctionParameterDecl(
+                 name='qpos1',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='qpos2',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                 ),
+             ),
+         ),
+         doc='Compute velocity by finite-differencing two positions.',
+     )),
+    ('mj_printFormattedModel',
+     FunctionDecl(
+         name='mj_printFormattedModel',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='filename',
+                 type=PointerType(
+                     inner_type=ValueType(name='char', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='float_format',
+                 type=PointerType(
+                     inner_type=ValueType(name='char', is_const=True),
+                 ),
+             ),
+         ),
+         doc='Print mjModel to text file, specifying format.',
+     )),
+    ('mjd_transitionFD',
+     FunctionDecl(
+         name='mjd_transitionFD',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='d',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjData'),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='eps',
+                 type=ValueType(name='mjtNum'),
+             ),
+             FunctionParameterDecl(
+                 name='flg_centered',
+                 type=ValueType(name='mjtByte'),
+             ),
+             FunctionParameterDecl(
+                 name='A',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum'),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='B',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum'),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='C',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum'),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='D',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjtNum'),
+                 ),
+             ),
+         ),
+         doc='Finite differenced transition matrices (control theory notation)\n'
+             '  d(x_next) = A*dx + B*du\n'
+             '  d(sensor) = C*dx + D*du\n'
+             '  required output matrix dimensions:\n'
+             '     A: (2*nv+na x 2*nv+na)\n'
+             '     B: (2*nv+na x nu)\n'
+             '     D: (nsensordata x 2*nv+na)\n'
+             '     C: (nsensordata x nu)',
+     )),
+    ('mjd_subQuat',
+     FunctionDecl(
+         name='mjd_subQuat',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='qa',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(4,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='qb',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(4,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='Da',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(9,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='Db',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(9,),
+                 ),
+             ),
+         ),
+         doc='Derivatives of mju_subQuat.',
+     )),
+    ('mjd_quatIntegrate',
+     FunctionDecl(
+         name='mjd_quatIntegrate',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='vel',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(3,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='scale',
+                 type=ValueType(name='mjtNum'),
+             ),
+             FunctionParameterDecl(
+                 name='Dquat',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(9,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='Dvel',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(9,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='Dscale',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(3,),
+                 ),
+             ),
+         ),
+         doc='Derivatives of mju_quatIntegrate.',
+     )),
+    ('mj_versionString',
+     FunctionDecl(
+         name='mj_versionString',
+         return_type=PointerType(
+             inner_type=ValueType(name='char', is_const=True),
+         ),
+         parameters=(),
+         doc='Return the version string.',
+     )),
+    ('mju_undocumented',
+     FunctionDecl(
+         name='mju_undocumented',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='res',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(3,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='n',
+                 type=ValueType(name='int'),
+             ),
+         ),
+         doc='',
+     )),
+    ('mj_defaultSolRefImp',
+     FunctionDecl(
+         name='mj_defaultSolRefImp',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='solref',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(mjNREF,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='solimp',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(mjNIMP,),
+                 ),
+             ),
+         ),
+         doc='Set default solver reference [solref]\n'
+             'and impedance parameters.',
+     )),
+    ('mj_energy',
+     FunctionDecl(
+         name='mj_energy',
+         return_type=ValueType(name='mjtNum'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='m',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjModel', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='d',
+                 type=PointerType(
+                     inner_type=ValueType(name='mjData', is_const=True),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='scale',
+                 type=ValueType(name='mjtNum'),
+             ),
+         ),
+         doc='Compute the kinetic energy.',
+     )),
+])
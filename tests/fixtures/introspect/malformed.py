@@ -0,0 +1,69 @@
+"""Malformed introspect metadata, for testing the diagnostics."""
+
+FUNCTIONS = dict([
+    ('mju_unknownType',
+     FunctionDecl(
+         name='mju_unknownType',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='res',
+                 type=FunctionPointerType(name='mjfCallback'),
+             ),
+         ),
+         doc='Takes a type unknown to the parser.',
+     )),
+    ('mju_scl3',
+     FunctionDecl(
+         name='mju_scl3',
+         return_type=ValueType(name='void'),
+         parameters=(
+             FunctionParameterDecl(
+                 name='res',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum'),
+                     extents=(3,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='vec',
+                 type=ArrayType(
+                     inner_type=ValueType(name='mjtNum', is_const=True),
+                     extents=(3,),
+                 ),
+             ),
+             FunctionParameterDecl(
+                 name='scl',
+                 type=ValueType(name='mjtNum'),
+             ),
+         ),
+         doc='Set res = vec*scl.',
+     )),
+])
+
+STRUCTS = dict([
+    ('mjMalformed',
+     StructDecl(
+         name='mjMalformed',
+         declname='struct mjMalformed_',
+         fields=(
+             StructFieldDecl(
+                 name='unterminated',
+                 type=ValueType(name='int'),
+                 doc='unterminated string,
+             ),
+         ),
+     )),
+    ('mjRecovered',
+     StructDecl(
+         name='mjRecovered',
+         declname='struct mjRecovered_',
+         fields=(
+             StructFieldDecl(
+                 name='count',
+                 type=ValueType(name='int'),
+                 doc='parsed after the syntax error above',
+             ),
+         ),
+     )),
+])
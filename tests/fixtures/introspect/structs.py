@@ -0,0 +1,290 @@
+# Copyright 2022 DeepMind Technologies Limited
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+# ==============================================================================
+"""Provides information about MuJoCo API structs.
+
+DO NOT EDIT. THIS FILE IS AUTOMATICALLY GENERATED.
+"""
+
+from typing import Mapping
+
+from .ast_nodes import AnonymousStructDecl
+from .ast_nodes import ArrayType
+from .ast_nodes import PointerType
+from .ast_nodes import StructDecl
+from .ast_nodes import StructFieldDecl
+from .ast_nodes import ValueType
+
+STRUCTS: Mapping[str, StructDecl] = dict([
+    ('mjModel',
+     StructDecl(
+         name='mjModel',
+         declname='struct mjModel_',
+         fields=(
+            StructFieldDecl(
+                name='nq',
+                type=ValueType(name='int'),
+                doc='number of generalized coordinates = dim(qpos)',
+            ),
+            StructFieldDecl(
+                name='nv',
+                type=ValueType(name='int'),
+                doc='number of degrees of freedom = dim(qvel)',
+            ),
+            StructFieldDecl(
+                name='nbody',
+                type=ValueType(name='int'),
+                doc='number of bodies',
+            ),
+            StructFieldDecl(
+                name='nkey',
+                type=ValueType(name='int'),
+                doc='number of keyframes',
+            ),
+            StructFieldDecl(
+                name='body_parentid',
+                type=PointerType(
+                    inner_type=ValueType(name='int'),
+                ),
+                doc="id of body's parent",
+                array_extent=('nbody',),
+            ),
+            StructFieldDecl(
+                name='body_pos',
+                type=PointerType(
+                    inner_type=ValueType(name='mjtNum'),
+                ),
+                doc='position offset rel. to parent body',
+                array_extent=('nbody', 3),
+            ),
+            StructFieldDecl(
+                name='body_quat',
+                type=PointerType(
+                    inner_type=ValueType(name='mjtNum'),
+                ),
+                doc='orientation offset rel. to parent body',
+                array_extent=('nbody', 4),
+            ),
+            StructFieldDecl(
+                name='jnt_type',
+                type=PointerType(
+                    inner_type=ValueType(name='int'),
+                ),
+                doc='type of joint (mjtJoint)',
+                array_extent=('njnt',),
+            ),
+            StructFieldDecl(
+                name='key_qpos',
+                type=PointerType(
+                    inner_type=ValueType(name='mjtNum'),
+                ),
+                doc='key position',
+                array_extent=('nkey', 'nq'),
+            ),
+            StructFieldDecl(
+                name='geom_solref',
+                type=PointerType(
+                    inner_type=ValueType(name='mjtNum'),
+                ),
+                doc='constraint solver reference: contact',
+                array_extent=('ngeom', 'mjNREF'),
+            ),
+            StructFieldDecl(
+                name='names',
+                type=PointerType(
+                    inner_type=ValueType(name='char'),
+                ),
+                doc='names of all objects, 0-terminated',
+                array_extent=('nnames',),
+            ),
+            StructFieldDecl(
+                name='name_bodyadr',
+                type=PointerType(
+                    inner_type=ValueType(name='int'),
+                ),
+                doc='body name pointers',
+                array_extent=('nbody',),
+            ),
+            StructFieldDecl(
+                name='mat_rgba',
+                type=PointerType(
+                    inner_type=ValueType(name='float'),
+                ),
+                doc='rgba',
+                array_extent=('nmat', 4),
+            ),
+            StructFieldDecl(
+                name='tex_data',
+                type=PointerType(
+                    inner_type=ValueType(name='unsigned char'),
+                ),
+                doc='pixel values',
+                array_extent=('ntexdata',),
+            ),
+         ),
+     )),
+    ('mjOption',
+     StructDecl(
+         name='mjOption',
+         declname='struct mjOption_',
+         fields=(
+            StructFieldDecl(
+                name='timestep',
+                type=ValueType(name='mjtNum'),
+                doc='timestep',
+            ),
+            StructFieldDecl(
+                name='impratio',
+                type=ValueType(name='mjtNum'),
+                doc='ratio of friction-to-normal contact impedance',
+            ),
+            StructFieldDecl(
+                name='tolerance',
+                type=ValueType(name='mjtNum'),
+                doc='main solver tolerance',
+            ),
+            StructFieldDecl(
+                name='gravity',
+                type=ArrayType(
+                    inner_type=ValueType(name='mjtNum'),
+                    extents=(3,),
+                ),
+                doc='gravitational acceleration',
+            ),
+            StructFieldDecl(
+                name='o_solref',
+                type=ArrayType(
+                    inner_type=ValueType(name='mjtNum'),
+                    extents=(mjNREF,),
+                ),
+                doc='solref',
+            ),
+            StructFieldDecl(
+                name='integrator',
+                type=ValueType(name='int'),
+                doc='integration mode (mjtIntegrator)',
+            ),
+            StructFieldDecl(
+                name='iterations',
+                type=ValueType(name='int'),
+                doc='maximum number of main solver iterations',
+            ),
+            StructFieldDecl(
+                name='noslip_iterations',
+                type=ValueType(name='int'),
+                doc='maximum number of noslip solver iterations',
+            ),
+            StructFieldDecl(
+                name='disableflags',
+                type=ValueType(name='int'),
+                doc='bit flags for disabling standard features',
+            ),
+            StructFieldDecl(
+                name='reserved',
+                type=ValueType(name='int'),
+                doc='',
+            ),
+         ),
+     )),
+    ('mjVisual',
+     StructDecl(
+         name='mjVisual',
+         declname='struct mjVisual_',
+         fields=(
+            StructFieldDecl(
+                name='global',
+                type=AnonymousStructDecl(
+                    fields=(
+                        StructFieldDecl(
+                            name='cameraid',
+                            type=ValueType(name='int'),
+                            doc='initial camera id (-1: free)',
+                        ),
+                        StructFieldDecl(
+                            name='fovy',
+                            type=ValueType(name='float'),
+                            doc='y field-of-view of free camera (degrees)',
+                        ),
+                        StructFieldDecl(
+                            name='ellipsoidinertia',
+                            type=ValueType(name='int'),
+                            doc='geom for inertia visualization (0: box, 1: ellipsoid)',
+                        ),
+                    ),
+                ),
+                doc='global parameters',
+            ),
+            StructFieldDecl(
+                name='map',
+                type=AnonymousStructDecl(
+                    fields=(
+                        StructFieldDecl(
+                            name='znear',
+                            type=ValueType(name='float'),
+                            doc='near clipping plane (relative to extent)',
+                        ),
+                        StructFieldDecl(
+                            name='zfar',
+                            type=ValueType(name='float'),
+                            doc='far clipping plane (relative to extent)',
+                        ),
+                        StructFieldDecl(
+                            name='haze',
+                            type=ValueType(name='float'),
+                            doc='haze ratio',
+                        ),
+                    ),
+                ),
+                doc='mapping',
+            ),
+            StructFieldDecl(
+                name='headlight',
+                type=AnonymousStructDecl(
+                    fields=(
+                        StructFieldDecl(
+                            name='active',
+                            type=ValueType(name='mjtByte'),
+                            doc='is headlight active',
+                        ),
+                    ),
+                ),
+                doc='headlight',
+            ),
+            StructFieldDecl(
+                name='rgba',
+                type=AnonymousStructDecl(
+                    fields=(
+                        StructFieldDecl(
+                            name='fog',
+                            type=ArrayType(
+                                inner_type=ValueType(name='float'),
+                                extents=(4,),
+                            ),
+                            doc='fog',
+                        ),
+                        StructFieldDecl(
+                            name='haze',
+                            type=ArrayType(
+                                inner_type=ValueType(name='float'),
+                                extents=(4,),
+                            ),
+                            doc='haze',
+                        ),
+                    ),
+                ),
+                doc='rgba',
+            ),
+         ),
+     )),
+])
@@ -0,0 +1,37 @@
+//---------------------------------- Quaternions and poses ----------------------------------------
+
+// Rotate vector by quaternion.
+MJAPI void mju_rotVecQuat(mjtNum res[3], const mjtNum vec[3], const mjtNum quat[4]);
+
+// Convert quaternion to 3D rotation matrix.
+MJAPI void mju_quat2Mat(mjtNum res[9], const mjtNum quat[4]);
+
+// Convert 3D rotation matrix to quaternion.
+MJAPI void mju_mat2Quat(mjtNum quat[4], const mjtNum mat[9]);
+
+// Normalize vector, return length before normalization.
+MJAPI mjtNum mju_normalize3(mjtNum vec[3]);
+
+// Multiply two poses.
+MJAPI void mju_mulPose(mjtNum posres[3], mjtNum quatres[4],
+                       const mjtNum pos1[3], const mjtNum quat1[4],
+                       const mjtNum pos2[3], const mjtNum quat2[4]);
+
+// Conjugate pose, corresponding to the opposite spatial transformation.
+MJAPI void mju_negPose(mjtNum posres[3], mjtNum quatres[4], const mjtNum pos[3], const mjtNum quat[4]);
+
+// Transform vector by pose.
+MJAPI void mju_trnVecPose(mjtNum res[3], const mjtNum pos[3], const mjtNum quat[4], const mjtNum vec[3]);
+
+// Multiply quaternions.
+MJAPI void mju_mulQuat(mjtNum res[4], const mjtNum quat1[4], const mjtNum quat2[4]);
+
+// Compute time-derivative of quaternion, given 3D rotational velocity.
+MJAPI void mju_derivQuat(mjtNum res[4], const mjtNum quat[4], const mjtNum vel[3]);
+
+// Convert axisAngle to quaternion.
+MJAPI void mju_axisAngle2Quat(mjtNum res[4], const mjtNum axis[3], mjtNum angle);
+
+// Set default solver reference [solref]
+// and impedance parameters.
+MJAPI void mj_defaultSolRefImp(mjtNum solref[mjNREF], mjtNum solimp[mjNIMP]);
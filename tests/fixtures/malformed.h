@@ -0,0 +1,45 @@
+// Copy vector.
+MJAPI void mju_copy3(mjtNum res[3], const mjtNum data[3]);
+
+// Function pointer parameter.
+MJAPI void mj_forEach(void (*callback)(int), int n);
+
+// Unnamed parameter.
+MJAPI void mju_unnamed(const mjtNum[3]);
+
+MJAPI extern int mj_someGlobal;
+
+struct mjMalformed_ {             // struct with unrecognized declarations
+  int      ngood;                 // recognized field
+  int      na, nb;                // multiple declarators
+  void     (*callback)(int);      // function pointer
+  mjtNum   empty[ ];              // empty dimension
+#ifdef mjUSEDOUBLE
+  double   precise;               // recognized field
+#endif
+};
+
+typedef enum mjtMalformed_ {      // enum with an unevaluable value
+  mjMAL_A             = 0,        // first
+  mjMAL_B             = mjMAL_A + 2,
+  mjMAL_C                         // never reached
+} mjtMalformed;
+
+typedef enum mjtOverflow_ {       // enum with overflowing shifts
+  mjOVF_A             = 1<<3,     // valid
+  mjOVF_B             = 1<<64,    // shift by the bit width
+  mjOVF_C             = 1
+} mjtOverflow;
+
+typedef enum mjtNegativeShift_ {  // enum with a negative shift
+  mjNEG_A             = 1<<-1     // negative shift
+} mjtNegativeShift;
+
+typedef enum mjtLostBits_ {       // enum with bits shifted out
+  mjLOST_A            = 4<<62     // shifted out
+} mjtLostBits;
+
+typedef enum mjtLast_ {           // enum with an overflowing implicit value
+  mjLAST_A            = 9223372036854775807,
+  mjLAST_B                        // overflows
+} mjtLast;
@@ -0,0 +1,4 @@
+#define MJMODEL_MALFORMED                                       \
+  X( int,     mal_,  good,         nbody, 1                   ) \
+  X( int,     mal_,  missing,      nbody                      ) \
+  X( mjtNum,  mal_,  unclosed,     njnt,  MJ_M(jnt_qposadr    )
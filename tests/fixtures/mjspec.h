@@ -0,0 +1,85 @@
+//---------------------------------- element structs -----------------------------------------------
+
+typedef struct mjsElement_ {       // element type, do not modify
+  mjtObj elemtype;                 // element type
+  uint64_t signature;              // compilation signature
+} mjsElement;
+
+typedef struct mjsOrientation_ {   // alternative orientation specifiers
+  mjtOrientation type;             // active orientation specifier
+  double axisangle[4];             // axis and angle
+} mjsOrientation;
+
+typedef struct mjsBody_ {          // body specification
+  mjsElement* element;             // element type
+  mjString* childclass;            // childclass name
+
+  // body frame
+  double pos[3];                   // frame position
+  double quat[4];                  // frame orientation
+  mjsOrientation alt;              // frame alternative orientation
+
+  // inertial frame
+  double mass;                     // mass
+  double fullinertia[6];           // non-axis-aligned inertia matrix
+
+  // other
+  mjtByte mocap;                   // is this a mocap body
+  mjDoubleVec* userdata;           // user data
+  mjStringVec* textures;           // texture names
+  mjString* info;                  // message appended to compiler errors
+} mjsBody;
+
+typedef struct mjsGeom_ {          // geom specification
+  mjsElement* element;             // element type
+  mjtGeom type;                    // geom type
+  double size[3];                  // geom size
+  int contype;                     // contact type
+  mjIntVec* userdata_int;          // user data as integers
+  mjFloatVec* vert;                // vertex positions
+  float rgba[4];                   // rgba when material is omitted
+  double solref[mjNREF];           // solver reference
+  void* plugin_data;               // plugin instance data
+  int reserved;
+} mjsGeom;
+
+//---------------------------------- tree elements -------------------------------------------------
+
+// Add child body to body, return child.
+MJAPI mjsBody* mjs_addBody(mjsBody* body, const mjsDefault* def);
+
+// Add geom to body.
+MJAPI mjsGeom* mjs_addGeom(mjsBody* body, const mjsDefault* def);
+
+// Add frame to body.
+MJAPI mjsFrame* mjs_addFrame(mjsBody* body, mjsFrame* parentframe);
+
+//---------------------------------- non-tree elements ---------------------------------------------
+
+// Add actuator.
+MJAPI mjsActuator* mjs_addActuator(mjSpec* s, const mjsDefault* def);
+
+//---------------------------------- find and delete -----------------------------------------------
+
+// Find body in spec by name.
+MJAPI mjsBody* mjs_findBody(mjSpec* s, const char* name);
+
+// Find element in spec by name.
+MJAPI mjsElement* mjs_findElement(mjSpec* s, mjtObj type, const char* name);
+
+// Find child body by name.
+MJAPI mjsBody* mjs_findChild(mjsBody* body, const char* name);
+
+// Delete object corresponding to the given element.
+MJAPI int mjs_delete(mjSpec* spec, mjsElement* element);
+
+// Add keyframe to model, fill from arrays.
+MJAPI mjsKey* mjs_addKeyFrom(mjSpec* s, const double* qpos, int nq);
+
+//---------------------------------- attribute setters ---------------------------------------------
+
+// Copy text to string.
+MJAPI void mjs_setString(mjString* dest, const char* text);
+
+// Get string contents.
+MJAPI const char* mjs_getString(const mjString* source);
@@ -0,0 +1,6 @@
+typedef enum mjtCamera_ {         // type of camera
+  mjCAMERA_FREE       = 0,        // free camera
+  mjCAMERA_TRACKING,              // tracking camera; uses trackbodyid
+  mjCAMERA_FIXED,                 // fixed camera; uses fixedcamid
+  mjCAMERA_USER                   // user is responsible for setting OpenGL camera
+} mjtCamera;
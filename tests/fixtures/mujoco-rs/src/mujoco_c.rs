@@ -0,0 +1,39 @@
+/* automatically generated by rust-bindgen 0.71.1 */
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct mjModel_ {
+    pub nq: ::std::os::raw::c_int,
+    pub nv: ::std::os::raw::c_int,
+    pub nbody: ::std::os::raw::c_int,
+    pub body_parentid: *mut ::std::os::raw::c_int,
+    pub body_jntnum: *mut ::std::os::raw::c_int,
+    pub body_jntadr: *mut ::std::os::raw::c_int,
+    pub body_pos: *mut mjtNum,
+    pub body_quat: *mut mjtNum,
+    pub jnt_type: *mut ::std::os::raw::c_int,
+    pub jnt_bodyid: *mut ::std::os::raw::c_int,
+    pub jnt_limited: *mut mjtByte,
+    pub jnt_qposadr: *mut ::std::os::raw::c_int,
+    pub jnt_qpos0: *mut mjtNum,
+    pub names: *mut ::std::os::raw::c_char,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct mjData_ {
+    pub qpos: *mut mjtNum,
+    pub qM: *mut mjtNum,
+    pub xpos: *mut mjtNum,
+    pub xquat: *mut mjtNum,
+}
+unsafe extern "C" {
+    pub fn mju_add3(res: *mut mjtNum, vec1: *const mjtNum, vec2: *const mjtNum);
+    pub fn mju_dot3(vec1: *const mjtNum, vec2: *const mjtNum) -> mjtNum;
+    pub fn mju_zero(res: *mut mjtNum, n: ::std::os::raw::c_int);
+    pub fn mj_sizeModel(m: *const mjModel) -> ::std::os::raw::c_int;
+    pub fn mj_resetData(m: *const mjModel, d: *mut mjData);
+    pub fn mj_name2id(m: *const mjModel, type_: ::std::os::raw::c_int, name: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    pub fn mjd_transitionFD(m: *const mjModel, d: *mut mjData, eps: mjtNum, flg_centered: mjtByte,
+                            A: *mut mjtNum, B: *mut mjtNum, C: *mut mjtNum, D: *mut mjtNum);
+    pub fn mj_versionString() -> *const ::std::os::raw::c_char;
+}
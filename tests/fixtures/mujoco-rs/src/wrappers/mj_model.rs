@@ -0,0 +1,33 @@
+use crate::mujoco_c::*;
+
+impl MjModel {
+    /// Returns the size of the buffer needed to hold the model, see `mj_sizeModel`.
+    pub fn size_model(&self) -> i32 {
+        unsafe { mj_sizeModel(self.ffi()) }
+    }
+
+    /// Returns the id of the named object, or -1 (note: mj_name2id is only wrapped by name_to_id).
+    pub fn name_to_id(&self, type_: MjtObj, name: &CStr) -> i32 {
+        unsafe { mujoco_c::mj_name2id(self.ffi(), type_ as i32, name.as_ptr()) }
+    }
+
+    array_slice_dyn! {
+        body_parentid: &[i32; "id of body's parent"; ffi().nbody],
+        body_pos: &[[MjtNum; 3] [cast]; "position offset rel. to parent body: see mju_zero(res, n)"; ffi().nbody],
+        names: &[i8; "names of all objects, 0-terminated"; ffi().nnames]
+    }
+}
+
+info_method! { Model, ffi(), body,
+    [parentid: 1,
+     jntnum: 1],
+    [],
+    []
+}
+
+impl Default for MjModelInfo {
+    fn default() -> Self {
+        assert_eq!(Point { nv: 0 }, Point::default());
+        Self { nq: 0 }
+    }
+}
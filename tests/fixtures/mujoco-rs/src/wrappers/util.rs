@@ -0,0 +1,6 @@
+/// Adds 3D vectors.
+pub fn mju_add_3(res: &mut [MjtNum; 3], vec_1: &[MjtNum; 3], vec_2: &[MjtNum; 3]) {
+    unsafe { mujoco_c::mju_add3(res.as_mut_ptr(), vec_1.as_ptr(), vec_2.as_ptr()) }
+}
+
+info_with_view!(Data, body, [xpos: MjtNum], [], M: Deref<Target = MjModel>);
@@ -0,0 +1,71 @@
+//---------------------------------- Vector math -------------------------------------------------
+
+// Set res = vec1 + vec2.
+MJAPI void mju_add3(mjtNum res[3], const mjtNum vec1[3], const mjtNum vec2[3]);
+
+// Return dot-product of vec1 and vec2.
+MJAPI mjtNum mju_dot3(const mjtNum vec1[3], const mjtNum vec2[3]);
+
+// Convert quaternion to 3D rotation matrix.
+MJAPI void mju_quat2Mat(mjtNum res[9], const mjtNum quat[4]);
+
+// Multiply quaternions.
+MJAPI void mju_mulQuat(mjtNum res[4], const mjtNum quat1[4], const mjtNum quat2[4]);
+
+// Set res = 0.
+MJAPI void mju_zero(mjtNum* res, int n);
+
+//---------------------------------- Initialization ----------------------------------------------
+
+// Return size of buffer needed to hold model.
+MJAPI int mj_sizeModel(const mjModel* m);
+
+// Reset data to defaults.
+MJAPI void mj_resetData(const mjModel* m, mjData* d);
+
+// Get id of object with the specified mjtObj type and name, returns -1 if id not found.
+MJAPI int mj_name2id(const mjModel* m, int type, const char* name);
+
+// Set solver parameters to default values.
+MJAPI void mj_setConst(mjModel* m, mjData* d);
+
+// Compute velocity by finite-differencing two positions.
+MJAPI void mj_differentiatePos(const mjModel* m, mjtNum* qvel, mjtNum dt,
+                               const mjtNum* qpos1, const mjtNum* qpos2);
+
+// Print mjModel to text file, specifying format.
+MJAPI void mj_printFormattedModel(const mjModel* m, const char* filename, const char* float_format);
+
+//---------------------------------- Derivatives -------------------------------------------------
+
+// Finite differenced transition matrices (control theory notation)
+//   d(x_next) = A*dx + B*du
+//   d(sensor) = C*dx + D*du
+//   required output matrix dimensions:
+//      A: (2*nv+na x 2*nv+na)
+//      B: (2*nv+na x nu)
+//      D: (nsensordata x 2*nv+na)
+//      C: (nsensordata x nu)
+MJAPI void mjd_transitionFD(const mjModel* m, mjData* d, mjtNum eps, mjtByte flg_centered,
+                            mjtNum* A, mjtNum* B, mjtNum* C, mjtNum* D);
+
+// Derivatives of mju_subQuat.
+MJAPI void mjd_subQuat(const mjtNum qa[4], const mjtNum qb[4], mjtNum Da[9], mjtNum Db[9]);
+
+// Derivatives of mju_quatIntegrate.
+MJAPI void mjd_quatIntegrate(const mjtNum vel[3], mjtNum scale,
+                             mjtNum Dquat[9], mjtNum Dvel[9], mjtNum Dscale[3]);
+
+//---------------------------------- Miscellaneous -----------------------------------------------
+
+// Return the version string.
+MJAPI const char* mj_versionString(void);
+
+MJAPI void mju_undocumented(mjtNum res[3], int n);
+
+// Set default solver reference [solref]
+// and impedance parameters.
+MJAPI void mj_defaultSolRefImp(mjtNum solref[mjNREF], mjtNum solimp[mjNIMP]);
+
+// Compute the kinetic energy.
+MJAPI mjtNum mj_energy(const mjModel* m, const mjData* d, mjtNum scale);
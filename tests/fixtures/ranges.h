@@ -0,0 +1,26 @@
+struct mjModel_ {
+  // sizes needed at mjModel construction
+  int nbody;                      // number of bodies
+  int njnt;                       // number of joints
+
+  // bodies
+  int*      body_jntnum;          // number of joints for this body           (nbody x 1)
+  int*      body_jntadr;          // start addr of joints; -1: no joints      (nbody x 1)
+  int*      body_geomnum;         // number of geoms                          (nbody x 1)
+  int*      body_geomadr;         // start addr of geoms; -1: no geoms        (nbody x 1)
+
+  // joints
+  int*      jnt_qposadr;          // start addr in 'qpos' for joint's data    (njnt x 1)
+  int*      jnt_dofadr;           // start addr in 'qvel' for joint's data    (njnt x 1)
+
+  // meshes
+  int*      mesh_texcoordadr;     // texcoord data address; -1: no texcoord   (nmesh x 1)
+
+  // sensors
+  int*      sensor_adr;           // address in sensor array                  (nsensor x 1)
+};
+struct mjData_ {
+  mjtNum*  qpos;               // position                                       (nq x 1)
+  mjtNum*  qvel;               // velocity                                       (nv x 1)
+  mjtNum*  sensordata;         // sensor data array                              (nsensordata x 1)
+};
@@ -0,0 +1,39 @@
+#define MJMODEL_BODY                                            \
+  X( int,     body_, parentid,     nbody, 1                   ) \
+  X( int,     body_, jntnum,       nbody, 1                   ) \
+  X( int,     body_, jntadr,       nbody, 1                   ) \
+  X( int,     body_, dofnum,       nbody, 1                   ) \
+  X( int,     body_, dofadr,       nbody, 1                   ) \
+  X( mjtNum,  body_, pos,          nbody, 3                   )
+
+#define MJMODEL_JOINT                                           \
+  X( int,     jnt_,  type,         njnt,  1                   ) \
+  X( int,     jnt_,  qposadr,      njnt,  1                   ) \
+  X( int,     jnt_,  dofadr,       njnt,  1                   ) \
+  X( int,     jnt_,  bodyid,       njnt,  1                   )
+
+#define MJMODEL_DOF                                             \
+  X( int,     dof_,  bodyid,       nv,    1                   ) \
+  X( int,     dof_,  jntid,        nv,    1                   )
+
+#define MJMODEL_GEOM                                            \
+  X( int,     geom_, bodyid,       ngeom, 1                   )
+
+#define MJMODEL_TENDON                                          \
+  X( int,     tendon_, adr,        ntendon, 1                 ) \
+  X( int,     tendon_, num,        ntendon, 1                 )
+
+#define MJMODEL_MESH                                            \
+  X( int,     mesh_, texcoordadr,  nmesh, 1                   )
+
+#define MJMODEL_ACTUATOR                                        \
+  X( int,     actuator_, actadr,   nu,    1                   ) \
+  X( int,     actuator_, actnum,   nu,    1                   )
+
+#define MJMODEL_SENSOR                                          \
+  X( int,     sensor_, dim,        nsensor, 1                 ) \
+  X( int,     sensor_, adr,        nsensor, 1                 )
+
+#define MJDATA_ACTUATOR                                         \
+  X( mjtNum,  ,      ctrl,         nu,    1                   ) \
+  X( mjtNum,  ,      act,          na,    MJ_M(actuator_actnum) )
@@ -0,0 +1,31 @@
+//! Fake mujoco-rs source with generated regions, which are out of date.
+use crate::mujoco_c::*;
+
+impl MjData {
+    // mujoco-rs-utils begin: array slices
+    array_slice_dyn! {
+        qM: MjtNum => ffi().nM,
+        qpos: MjtNum => ffi().nq,
+        qvel: MjtNum => ffi().nv,
+        efc_J: MjtNum => [ffi().nefc, ffi().nv],
+        ten_J: MjtNum => [ffi().ntendon, ffi().nv],
+    }
+    // mujoco-rs-utils end: array slices
+}
+
+// mujoco-rs-utils begin: functions
+/// Set res = vec1 + vec2.
+pub fn mju_add_3(res: &mut [MjtNum; 3], vec_1: &[MjtNum; 3], vec_2: &[MjtNum; 3])  {
+    unsafe { mujoco_c::mju_add3(res.as_mut_ptr(), vec_1.as_ptr(), vec_2.as_ptr()) }
+}
+
+/// Return dot-product of vec1 and vec2.
+pub fn mju_dot_3(vec_1: &[MjtNum; 3], vec_2: &[MjtNum; 3]) -> f64  {
+    unsafe { mujoco_c::mju_dot3(vec_1.as_ptr(), vec_2.as_ptr()) }
+}
+
+/// Subtract vectors.
+pub fn mju_sub_3(res: &mut [MjtNum; 3], vec_1: &[MjtNum; 3], vec_2: &[MjtNum; 3])  {
+    unsafe { mujoco_c::mju_sub3(res.as_mut_ptr(), vec_1.as_ptr(), vec_2.as_ptr()) }
+}
+// mujoco-rs-utils end: functions
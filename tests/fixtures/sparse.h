@@ -0,0 +1,45 @@
+struct mjData_ {
+  // variable sizes
+  int     nefc;              // number of constraints
+  int     nJ;                // number of non-zeros in constraint Jacobian
+
+  // computed by mj_fwdPosition/mj_makeM
+  mjtNum* qM;                // total inertia (sparse)                           (nM x 1)
+  mjtNum* M;                 // reduced inertia (compressed sparse row)          (nC x 1)
+  int*    M_rownnz;          // reduced inertia: non-zeros in each row           (nv x 1)
+  int*    M_rowadr;          // reduced inertia: address of each row in M_colind (nv x 1)
+  int*    M_colind;          // reduced inertia: column indices of non-zeros     (nC x 1)
+
+  // computed by mj_fwdPosition/mj_transmission
+  mjtNum* actuator_moment;   // actuator moments                                 (nJmom x 1)
+  int*    moment_rownnz;     // number of non-zeros in actuator_moment row       (nu x 1)
+  int*    moment_rowadr;     // row start address in colind array                (nu x 1)
+  int*    moment_colind;     // column indices in sparse Jacobian                (nJmom x 1)
+
+  // computed by mj_fwdPosition/mj_tendon
+  mjtNum* ten_J;             // tendon Jacobian                                  (ntendon x nv)
+  int*    ten_J_rownnz;      // number of non-zeros in Jacobian row              (ntendon x 1)
+  int*    ten_J_rowadr;      // row start address in colind array                (ntendon x 1)
+  int*    ten_J_colind;      // column indices in sparse Jacobian                (ntendon x nv)
+
+  // computed by mj_fwdVelocity/mj_makeConstraint
+  mjtNum* efc_J;             // constraint Jacobian                              (nJ x 1)
+  int*    efc_J_rownnz;      // number of non-zeros in constraint Jacobian row   (nefc x 1)
+  int*    efc_J_rowadr;      // row start address in colind array                (nefc x 1)
+  int*    efc_J_rowsuper;    // number of subsequent rows in supernode           (nefc x 1)
+  int*    efc_J_colind;      // column indices in constraint Jacobian            (nJ x 1)
+
+  // computed by mj_fwdVelocity/mjd_smooth_vel
+  int*    D_rownnz;          // non-zeros in each row                            (nv x 1)
+  int*    D_rowadr;          // address of each row in D_colind                  (nv x 1)
+  int*    D_diag;            // index of diagonal element                        (nv x 1)
+  int*    D_colind;          // column indices of non-zeros                      (nD x 1)
+  int*    B_rownnz;          // non-zeros in each row                            (nbody x 1)
+  int*    B_rowadr;          // address of each row in B_colind                  (nbody x 1)
+  int*    B_colind;          // column indices of non-zeros                      (nB x 1)
+  mjtNum* qDeriv;            // d (passive + actuator - bias) / d qvel           (nD x 1)
+
+  // computed by mj_fwdConstraint
+  int*    efc_AR_rownnz;     // number of non-zeros in AR                        (nefc x 1)
+  int*    efc_AR_rowadr;     // row start address in colind array                (nefc x 1)
+};
@@ -0,0 +1,7 @@
+struct mjuiDef_ {                 // UI item definition
+  int type;                       // type (mjtItem)
+  char name[mjMAXUINAME];         // name
+  struct {                        // type-specific properties
+    char other[mjMAXUITEXT];      // string with type-specific properties
+  } props;
+};
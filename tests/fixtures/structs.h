@@ -0,0 +1,96 @@
+struct mjModel_ {
+  // sizes needed at mjModel construction
+  int nq;                         // number of generalized coordinates = dim(qpos)
+  int nv;                         // number of degrees of freedom = dim(qvel)
+  int nbody;                      // number of bodies
+  int nkey;                       // number of keyframes
+
+  // bodies
+  int*      body_parentid;        // id of body's parent                      (nbody x 1)
+  int*      body_jntnum;          // number of joints for this body           (nbody x 1)
+  int*      body_jntadr;          // start addr of joints; -1: no joints      (nbody x 1)
+  mjtNum*   body_pos;             // position offset rel. to parent body      (nbody x 3)
+  mjtNum*   body_quat;            // orientation offset rel. to parent body   (nbody x 4)
+  int*      jnt_type;             // type of joint (mjtJoint)                 (njnt x 1)
+  int*      jnt_bodyid;           // id of joint's body                       (njnt x 1)
+  mjtNum*   cam_mat0;             // global orientation in qpos0              (ncam x 9)
+  mjtNum*   key_qpos;             // key position                             (nkey x nq)
+  mjtNum*   key_mpos;             // key mocap position                       (nkey x nmocap*3)
+  mjtNum*   geom_solref;          // constraint solver reference: contact     (ngeom x mjNREF)
+  int*      tuple_objid;          // object id                                (ntupledata x 1)
+  char*     names;                // names of all objects, 0-terminated       (nnames x 1)
+  int*      name_bodyadr;         // body name pointers                       (nbody x 1)
+  mjtNum*   sensor_user;          // user data                                (nsensor x nuser_sensor)
+  mjtByte*  jnt_limited;          // does joint have limits (mjtByte)         (njnt x 1)
+  int*      jnt_qposadr;          // start addr in 'qpos' for joint's data    (njnt x 1)
+  mjtNum*   jnt_qpos0;            // joint position in qpos0           (1 x sum(jnt_qposadr))
+  mjtNum*   jnt_qposcoef;         // polynomial position coefficients   (12 x sum(jnt_qposadr))
+  mjtNum*   mat_texrepeat;        // texture repetition for 2d mapping        (nmat x 2)
+  float*    mat_rgba;             // rgba                                     (nmat x 4)
+  unsigned char* tex_data;        // pixel values                             (ntexdata x 1)
+};
+struct mjData_ {
+  mjtNum*  efc_J;              // constraint Jacobian                            (nefc x nv)
+  mjtNum*  qM;                 // total inertia (sparse)                         (nM x 1)
+  mjtNum*  qpos;               // position                                       (nq x 1)
+  int*     efc_J_rownnz;       // number of non-zeros in Jacobian row            (nefc x 1)
+  mjtNum*  ten_J;              // tendon Jacobian                                (ntendon x nv)
+  mjtNum*  efc_AR;             // J*inv(M)*J' + R                                (nefc x nefc)
+  mjtNum*  cdof_dot;           // time-derivative of cdof                        (nv x 6)
+  mjtNum*  flexedge_J;         // flex edge Jacobian                             (nflexedge x nv x 3)
+};
+struct mjOption_ {                // physics options
+  // timing parameters
+  mjtNum timestep;                // timestep
+  mjtNum impratio;                // ratio of friction-to-normal contact impedance
+  mjtNum tolerance;               // main solver tolerance
+  mjtNum gravity[3];              // gravitational acceleration
+  mjtNum o_solref[mjNREF];        // solref
+  int integrator;                 // integration mode (mjtIntegrator)
+  int iterations;                 // maximum number of main solver iterations
+  int noslip_iterations;          // maximum number of noslip solver iterations
+  int disableflags;               // bit flags for disabling standard features
+  int reserved;
+};
+struct mjvCamera_ {               // abstract camera
+  int      type;                  // camera type (mjtCamera)
+  int      fixedcamid;            // fixed camera id
+  mjtNum   lookat[3];             // lookat point
+  mjtNum   distance;              // distance to lookat point or tracked body
+  float    frustum[2][3];         // frustum corners
+};
+struct mjVisual_ {                // visualization options
+  struct {                        // global parameters
+    int   cameraid;               // initial camera id (-1: free)
+    float fovy;                   // y field-of-view of free camera (degrees)
+    int   ellipsoidinertia;       // geom for inertia visualization (0: box, 1: ellipsoid)
+  } global;
+
+  struct {                        // mapping
+    float znear;                  // near clipping plane (relative to extent)
+    float zfar;                   // far clipping plane (relative to extent)
+    float haze;                   // haze ratio
+  } map;
+
+  struct {                        // headlight
+    mjtByte active;               // is headlight active
+  } headlight;
+
+  struct {                        // rgba
+    float fog[4];                 // fog
+    float haze[4];                // haze
+  } rgba;
+};
+struct mjStatistic_ {             // model statistics (in qpos0)
+  mjtNum meaninertia;             // mean diagonal inertia
+  mjtNum center[3];               // center of model
+};
+struct mjvFigure_ {
+  int flg_legend;                 // show legend
+  float figurergba[4];            // figure color and alpha
+  char title[1000];               // figure title
+  char xlabel[100];               // x-axis label
+  char linename[mjMAXLINE][100];  // line names for legend
+  float linergb[mjMAXLINE][3];    // line colors
+  char reserved[0];               // zero-length string, skipped
+};
@@ -0,0 +1,8 @@
+array_slice_dyn! {
+{{#single}}
+    {{name}}: {{#item_size}}[{{type}}; {{item_size}}]{{/item_size}}{{^item_size}}{{type}}{{/item_size}} => {{length}},
+{{/single}}
+{{#nd}}
+    {{name}}: {{type}} => [{{shape}}],
+{{/nd}}
+}
@@ -0,0 +1,6 @@
+{{#scalars}}
+getter!({{name}}, {{#reference}}&{{/reference}}{{type}}, "{{doc}}"{{#enum}}, coerce{{/enum}});
+{{/scalars}}
+{{#arrays}}
+getter!({{name}}, &[{{type}}; {{size}}], "{{doc}}");
+{{/arrays}}
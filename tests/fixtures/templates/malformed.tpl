@@ -0,0 +1,3 @@
+{{#scalars}}
+{{name}}: {{type}}
+{{/arrays}}
@@ -0,0 +1,3 @@
+{{#scalars}}
+{{name}}: {{c_type}}
+{{/scalars}}
@@ -0,0 +1,8 @@
+{{! A future macro syntax, listing each attribute with its length }}
+{{#groups}}
+views! { {{class}}::{{item}} {
+{{#entries}}
+    {{#prefix}}[{{prefix}}] {{/prefix}}{{name}}: {{type}}{{#cast}} as cast{{/cast}} * {{length}}{{#external}} (external){{/external}}{{^last}},{{/last}}
+{{/entries}}
+}{{#data}} where M: Deref<Target = MjModel>{{/data}} }
+{{/groups}}
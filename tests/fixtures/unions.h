@@ -0,0 +1,16 @@
+struct mjuiItem_ {                // UI item
+  int type;                       // type (mjtItem)
+  int state;                      // 0: disable, 1: enable, 2+: use predicate
+  int move;                       // a Rust keyword, renamed by bindgen
+  union {                         // type-specific properties
+    int single;                   // check and button
+    double slider[2];             // slider range
+  };
+  union {                         // value
+    float rgba[4];                // color
+    int index;                    // selected index
+  } value;
+  struct {                        // layout
+    int rect[4];                  // rectangle
+  };
+};
@@ -0,0 +1,406 @@
+//! Golden-file tests of the subcommands, run on the trimmed MuJoCo headers and documentation in tests/fixtures.
+//! The expected outputs (and diagnostics of malformed inputs) are stored in tests/golden.
+//! Golden files of Rust code are also checked to parse (with syn), though they are not type-checked.
+//! To accept intentional changes of the output,
+//! run the tests with the `UPDATE_GOLDEN` environment variable set, e.g., `UPDATE_GOLDEN=1 cargo test`.
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::{env, fs};
+
+
+/// Returns the tests directory.
+fn tests_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+
+/// Runs the subcommand given by `args` in the fixtures directory.
+fn run(name: &str, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_mujoco-rs-utils"))
+        .args(args)
+        .current_dir(tests_dir().join("fixtures"))
+        .output()
+        .expect("failed to run mujoco-rs-utils");
+    assert!(output.status.success(), "{name} failed: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+
+/// Runs the subcommand given by `args`, which must fail, and compares its error (stderr) to the golden file `name`.txt.
+fn check_golden_error(name: &str, args: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_mujoco-rs-utils"))
+        .args(args)
+        .current_dir(tests_dir().join("fixtures"))
+        .output()
+        .expect("failed to run mujoco-rs-utils");
+    assert!(!output.status.success(), "{name} succeeded, but it should fail");
+    compare_golden(name, &String::from_utf8(output.stderr).expect("error is not valid UTF-8"));
+}
+
+
+/// Runs the subcommand given by `args` and compares its output to the golden file `name`.txt.
+fn check_golden(name: &str, args: &[&str]) {
+    let output = run(name, args);
+    compare_golden(name, &String::from_utf8(output.stdout).expect("output is not valid UTF-8"));
+}
+
+
+/// Runs the subcommand given by `args` and compares its diagnostics (stderr) to the golden file `name`.txt.
+fn check_golden_diagnostics(name: &str, args: &[&str]) {
+    let output = run(name, args);
+    compare_golden(name, &String::from_utf8(output.stderr).expect("diagnostics are not valid UTF-8"));
+}
+
+
+/// Compares `actual` to the golden file `name`.txt. With `UPDATE_GOLDEN` set, the golden file is overwritten instead.
+fn compare_golden(name: &str, actual: &str) {
+    let golden_path = tests_dir().join("golden").join(format!("{name}.txt"));
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&golden_path, actual).expect("failed to write the golden file");
+        return;
+    }
+
+    let expected = fs::read_to_string(&golden_path).unwrap_or_else(|_| panic!(
+        "missing golden file {}, run with UPDATE_GOLDEN=1 to create it", golden_path.display()
+    ));
+    if let Some((line, (expected_line, actual_line))) = expected.lines().zip(actual.lines()).enumerate()
+        .find(|(_, (e, a))| e != a)
+    {
+        panic!(
+            "output of {name} differs from {} at line {}:\nexpected: {expected_line}\n  actual: {actual_line}\n\
+            run with UPDATE_GOLDEN=1 to accept the change", golden_path.display(), line + 1
+        );
+    }
+    assert!(
+        expected == actual,
+        "output of {name} differs from {} at the end, run with UPDATE_GOLDEN=1 to accept the change", golden_path.display()
+    );
+}
+
+
+#[test]
+fn create_views() {
+    check_golden("views", &["create-views", "indexer_xmacro.h"]);
+}
+
+#[test]
+fn create_typed_ids() {
+    check_golden("id_types", &["create-id-types"]);
+    check_golden("views_typed_ids", &["create-views", "ids_xmacro.h", "--typed-ids"]);
+    check_golden("views_typed_ids_commented", &["create-views", "ids_xmacro.h", "--typed-ids", "--model-header", "ids.h"]);
+    check_golden("array_slice_typed_ids", &["create-array-slice-macro-call", "ids.h", "ffi()", "mjModel_", "--typed-ids"]);
+}
+
+#[test]
+fn create_sparse_views() {
+    check_golden("sparse_views", &["create-sparse-views", "sparse.h", "self.model.ffi()", "--view-types"]);
+}
+
+#[test]
+fn create_range_accessors() {
+    check_golden("range_accessors", &["create-range-accessors", "ranges_xmacro.h"]);
+    check_golden("range_accessors_typed_ids", &["create-range-accessors", "ranges_xmacro.h", "--typed-ids", "--headers", "ranges.h"]);
+}
+
+#[test]
+fn create_name_lookups() {
+    check_golden("name_lookups", &["create-name-lookups", "indexer_xmacro.h"]);
+    check_golden("name_lookups_checked", &["create-name-lookups", "indexer_xmacro.h", "--model-header", "structs.h"]);
+}
+
+#[test]
+fn create_fixed_array_function_wrappers() {
+    check_golden("fixed_array_fn", &["create-fixed-array-function-wrappers", "mujoco.h", "--with-tests"]);
+    check_golden(
+        "fixed_array_fn_linalg", &["create-fixed-array-function-wrappers", "linalg.h", "--linalg", "nalgebra", "--linalg", "glam"]
+    );
+}
+
+#[test]
+fn create_model_methods() {
+    check_golden("model_methods_mjModel", &["create-model-methods", "mujoco.h", "mjModel", "mjData"]);
+    check_golden(
+        "model_methods_mjData",
+        &[
+            "create-model-methods", "mujoco.h", "mjData", "--test-instance", "MjData::new(&model)",
+            "--test-model", "MjModel::from_xml_string(\"<mujoco/>\").unwrap()"
+        ]
+    );
+}
+
+#[test]
+fn create_derivative_wrappers() {
+    check_golden("derivative_wrappers", &["create-derivative-wrappers", "derivatives.h", "--shapes", "derivatives.shapes"]);
+    check_golden("derivative_wrappers_mujoco_h", &["create-derivative-wrappers", "mujoco.h"]);
+}
+
+#[test]
+fn create_callbacks() {
+    check_golden("callbacks", &["create-callbacks", "callbacks.h"]);
+}
+
+#[test]
+fn create_types() {
+    check_golden("types_mjt", &["create-types", "APItypes.rst", "mjt"]);
+    check_golden("types_all", &["create-types", "APItypes.rst"]);
+}
+
+#[test]
+fn create_array_slice_macro_call() {
+    check_golden("array_slice_mjModel", &["create-array-slice-macro-call", "structs.h", "ffi()", "mjModel_"]);
+    check_golden("array_slice_mjData", &["create-array-slice-macro-call", "structs.h", "ffi()", "mjData_", "--view-types"]);
+}
+
+#[test]
+fn create_getters_setters() {
+    check_golden(
+        "getters_setters_mjOption",
+        &[
+            "create-getters-setters", "structs.h", "mjOption_", "--xml-reference", "XMLreference.rst",
+            "--overrides", "option.constraints", "--error-type"
+        ]
+    );
+    check_golden("getters_setters_mjVisual", &["create-getters-setters", "structs.h", "mjVisual_", "--overrides", "visual.constraints"]);
+    check_golden("getters_setters_mjvCamera", &["create-getters-setters", "structs.h", "mjvCamera_"]);
+    check_golden("getters_setters_mjvFigure", &["create-getters-setters", "structs.h", "mjvFigure_", "--error-type"]);
+    check_golden("getters_setters_mjuiDef", &["create-getters-setters", "strings.h", "mjuiDef_"]);
+}
+
+#[test]
+fn create_builder() {
+    check_golden("builder_mjOption", &["create-builder", "structs.h", "mjOption_", "--default-fn", "mj_defaultOption"]);
+    check_golden("builder_mjVisual", &["create-builder", "structs.h", "mjVisual_"]);
+    check_golden("builder_mjvCamera", &["create-builder", "structs.h", "mjvCamera_"]);
+}
+
+#[test]
+fn create_serde_impls() {
+    check_golden(
+        "serde_mjOption",
+        &["create-serde-impls", "structs.h", "mjOption_", "--enum-headers", "mjmodel.h", "--default-fn", "mj_defaultOption"]
+    );
+    check_golden("serde_mjvCamera", &["create-serde-impls", "structs.h", "mjvCamera_", "--enum-headers", "mjvisualize.h"]);
+    check_golden("serde_mjVisual", &["create-serde-impls", "structs.h", "mjVisual_"]);
+}
+
+#[test]
+fn create_layout_checks() {
+    check_golden("layout_checks", &["create-layout-checks", "structs.h"]);
+    check_golden("layout_checks_enums", &["create-layout-checks", "structs.h", "mjmodel.h", "--structs", "mjOption_"]);
+    check_golden("layout_checks_unions", &["create-layout-checks", "unions.h"]);
+}
+
+#[test]
+fn create_spec_wrappers() {
+    check_golden("spec_wrappers", &["create-spec-wrappers", "mjspec.h"]);
+    check_golden("spec_wrappers_mjsGeom", &["create-spec-wrappers", "mjspec.h", "--structs", "mjsGeom_"]);
+    check_golden_diagnostics("skipped_spec_wrappers", &["create-spec-wrappers", "mjspec.h", "--skipped-report", "text"]);
+}
+
+#[test]
+fn coverage() {
+    check_golden(
+        "coverage", &["coverage", "mujoco-rs", "mujoco.h", "structs.h", "--indexer-xmacro", "indexer_xmacro.h"]
+    );
+}
+
+#[test]
+fn mujoco_root() {
+    // The tree contains copies of the fixtures, so the outputs are the same as when given by path
+    check_golden(
+        "fixed_array_fn", &["create-fixed-array-function-wrappers", "--mujoco-root", "mujoco-root", "mujoco.h", "--with-tests"]
+    );
+    check_golden("types_mjt", &["create-types", "--mujoco-root", "mujoco-root", "APItypes.rst", "mjt"]);
+    check_golden(
+        "model_methods_mjModel", &["create-model-methods", "--mujoco-root", "mujoco-root", "functions.py", "mjModel", "mjData"]
+    );
+    check_golden(
+        "layout_checks_enums",
+        &["create-layout-checks", "--mujoco-root", "mujoco-root", "references.h", "mjmodel.h", "--structs", "mjOption_"]
+    );
+    check_golden_error(
+        "mujoco_root_not_found",
+        &["coverage", "mujoco-rs", "--mujoco-root", "mujoco-root", "mjdata.h", "--indexer-xmacro", "indexer_xmacro.h"]
+    );
+}
+
+#[test]
+fn introspect_input() {
+    // The introspect files describe (subsets of) the fixture headers, so the outputs are the same as from the headers
+    check_golden("fixed_array_fn", &["create-fixed-array-function-wrappers", "introspect/functions.py", "--with-tests"]);
+    check_golden("model_methods_mjModel", &["create-model-methods", "introspect/functions.py", "mjModel", "mjData"]);
+    check_golden(
+        "serde_mjOption",
+        &[
+            "create-serde-impls", "introspect/structs.py", "mjOption_", "--enum-headers", "introspect/enums.py",
+            "--default-fn", "mj_defaultOption"
+        ]
+    );
+    check_golden("builder_mjVisual", &["create-builder", "introspect/structs.py", "mjVisual_"]);
+    check_golden("name_lookups_checked", &["create-name-lookups", "indexer_xmacro.h", "--model-header", "introspect/structs.py"]);
+    check_golden("array_slice_introspect", &["create-array-slice-macro-call", "introspect/structs.py", "ffi()", "mjModel_"]);
+    check_golden_diagnostics("diagnostics_introspect", &["create-fixed-array-function-wrappers", "introspect/malformed.py"]);
+    // The entries after a syntax error are still parsed
+    check_golden("builder_introspect_recovered", &["create-builder", "introspect/malformed.py", "mjRecovered_"]);
+}
+
+#[test]
+fn templates() {
+    // The default templates are the current formats
+    check_golden("views", &["create-views", "indexer_xmacro.h", "--template", "../../templates/views.tpl"]);
+    check_golden(
+        "array_slice_mjData",
+        &["create-array-slice-macro-call", "structs.h", "ffi()", "mjData_", "--view-types", "--template", "../../templates/array_slice.tpl"]
+    );
+    check_golden(
+        "getters_setters_mjVisual",
+        &[
+            "create-getters-setters", "structs.h", "mjVisual_", "--overrides", "visual.constraints",
+            "--template", "../../templates/getters_setters.tpl"
+        ]
+    );
+
+    check_golden("views_template", &["create-views", "indexer_xmacro.h", "--template", "templates/views.tpl"]);
+    check_golden(
+        "array_slice_template", &["create-array-slice-macro-call", "structs.h", "ffi()", "mjData_", "--template", "templates/array_slice.tpl"]
+    );
+    check_golden(
+        "getters_setters_template", &["create-getters-setters", "structs.h", "mjOption_", "--template", "templates/getters_setters.tpl"]
+    );
+    check_golden_error(
+        "template_unknown_variable", &["create-getters-setters", "structs.h", "mjOption_", "--template", "templates/unknown_variable.tpl"]
+    );
+    check_golden_error("template_malformed", &["create-getters-setters", "structs.h", "mjOption_", "--template", "templates/malformed.tpl"]);
+}
+
+#[test]
+fn dry_run() {
+    check_golden(
+        "dry_run_array_slices",
+        &[
+            "create-array-slice-macro-call", "structs.h", "ffi()", "mjData_", "--template", "templates/array_slice.tpl",
+            "--write", "regions/mj_data.rs", "--region", "array slices", "--dry-run", "--color", "never"
+        ]
+    );
+    check_golden(
+        "dry_run_functions",
+        &[
+            "create-fixed-array-function-wrappers", "mujoco.h",
+            "--write", "regions/mj_data.rs", "--region", "functions", "--dry-run", "--color", "never"
+        ]
+    );
+    check_golden_error(
+        "region_missing", &["create-fixed-array-function-wrappers", "mujoco.h", "--write", "regions/mj_data.rs", "--region", "views"]
+    );
+}
+
+#[test]
+fn write_regions() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("mj_data.rs");
+    fs::copy(tests_dir().join("fixtures/regions/mj_data.rs"), &path).expect("failed to copy the regions fixture");
+    let path = path.to_str().unwrap();
+    run(
+        "write_regions",
+        &[
+            "create-array-slice-macro-call", "structs.h", "ffi()", "mjData_", "--template", "templates/array_slice.tpl",
+            "--write", path, "--region", "array slices"
+        ]
+    );
+    run("write_regions", &["create-fixed-array-function-wrappers", "mujoco.h", "--write", path, "--region", "functions"]);
+    compare_golden("regions_written", &fs::read_to_string(path).expect("failed to read the written file"));
+
+    // Regenerating an up-to-date region changes nothing
+    let output = run(
+        "write_regions", &["create-fixed-array-function-wrappers", "mujoco.h", "--write", path, "--region", "functions", "--dry-run"]
+    );
+    let output = String::from_utf8(output.stdout).expect("output is not valid UTF-8");
+    assert_eq!(output, format!("{path} [functions]: no changes\n"));
+}
+
+#[test]
+fn diagnostics_of_malformed_input() {
+    check_golden_diagnostics("diagnostics_functions", &["create-fixed-array-function-wrappers", "malformed.h"]);
+    check_golden_diagnostics(
+        "diagnostics_structs_enums",
+        &["create-serde-impls", "malformed.h", "mjMalformed_", "--enum-headers", "malformed.h"]
+    );
+    check_golden_diagnostics("diagnostics_views", &["create-views", "malformed_xmacro.h"]);
+    check_golden_diagnostics(
+        "diagnostics_overrides",
+        &["create-getters-setters", "structs.h", "mjOption_", "--overrides", "malformed.constraints"]
+    );
+    check_golden_diagnostics(
+        "diagnostics_shapes", &["create-derivative-wrappers", "derivatives.h", "--shapes", "malformed.shapes"]
+    );
+}
+
+#[test]
+fn skipped_report() {
+    check_golden_diagnostics(
+        "skipped_fixed_array_fn", &["create-fixed-array-function-wrappers", "mujoco.h", "--skipped-report", "text"]
+    );
+    check_golden_diagnostics(
+        "skipped_model_methods",
+        &["create-model-methods", "mujoco.h", "mjModel", "mjData", "--skipped-report", "json"]
+    );
+    check_golden_diagnostics(
+        "skipped_derivative_wrappers", &["create-derivative-wrappers", "derivatives.h", "--skipped-report", "text"]
+    );
+    check_golden_diagnostics(
+        "skipped_range_accessors", &["create-range-accessors", "ranges_xmacro.h", "--headers", "ranges.h", "--skipped-report", "text"]
+    );
+    check_golden_diagnostics("skipped_sparse_views", &["create-sparse-views", "sparse.h", "model.ffi()", "--skipped-report", "text"]);
+    check_golden_diagnostics("skipped_serde_impls", &["create-serde-impls", "structs.h", "mjvCamera_", "--skipped-report", "text"]);
+    check_golden_diagnostics(
+        "skipped_smoke_tests",
+        &[
+            "create-model-methods", "mujoco.h", "mjData", "--test-instance", "MjData::new(&model)",
+            "--test-model", "MjModel::from_xml_string(\"<mujoco/>\").unwrap()", "--skipped-report", "text"
+        ]
+    );
+    check_golden_diagnostics("skipped_callbacks", &["create-callbacks", "callbacks.h", "--skipped-report", "text"]);
+    check_golden_diagnostics("skipped_getters_setters", &["create-getters-setters", "structs.h", "mjvCamera_", "--skipped-report", "text"]);
+    check_golden_diagnostics(
+        "skipped_getters_setters_mjvFigure", &["create-getters-setters", "structs.h", "mjvFigure_", "--skipped-report", "text"]
+    );
+    check_golden_diagnostics(
+        "skipped_getters_setters_defaults",
+        &["create-getters-setters", "structs.h", "mjVisual_", "--overrides", "visual.constraints", "--skipped-report", "text"]
+    );
+}
+
+
+/// Golden files of the generators, whose outputs are Rust items.
+const RUST_GOLDENS: &[&str] = &[
+    "array_slice_template", "builder_introspect_recovered", "builder_mjOption", "builder_mjVisual", "builder_mjvCamera", "callbacks",
+    "derivative_wrappers", "derivative_wrappers_mujoco_h", "fixed_array_fn", "fixed_array_fn_linalg",
+    "getters_setters_template", "id_types", "layout_checks", "layout_checks_enums", "layout_checks_unions",
+    "model_methods_mjData", "model_methods_mjModel", "name_lookups", "name_lookups_checked", "range_accessors",
+    "range_accessors_typed_ids", "serde_mjOption", "serde_mjVisual", "serde_mjvCamera", "sparse_views", "spec_wrappers",
+    "spec_wrappers_mjsGeom", "types_all", "types_mjt", "views", "views_template", "views_typed_ids", "views_typed_ids_commented",
+];
+
+/// Sections of the getters/setters goldens, which are Rust items (unlike the macro entries).
+const RUST_SECTIONS: &[&str] = &["Processing string getters/setters", "Processing validated setters", "Processing default values"];
+
+#[test]
+fn goldens_parse_as_rust() {
+    let parse = |name: &str, code: &str| {
+        if let Err(error) = syn::parse_file(code) {
+            panic!("golden file {name}.txt is not valid Rust: {error}");
+        }
+    };
+
+    for name in RUST_GOLDENS {
+        parse(name, &fs::read_to_string(tests_dir().join("golden").join(format!("{name}.txt"))).unwrap());
+    }
+
+    for name in ["getters_setters_mjOption", "getters_setters_mjVisual", "getters_setters_mjvFigure", "getters_setters_mjuiDef"] {
+        let golden = fs::read_to_string(tests_dir().join("golden").join(format!("{name}.txt"))).unwrap();
+        // Sections start with the title between two separator lines
+        let sections = golden.split("-----------------------------------------\n").collect::<Vec<_>>();
+        for pair in sections.windows(2).filter(|pair| RUST_SECTIONS.contains(&pair[0].trim_end())) {
+            parse(name, pair[1]);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Tests of the multi-dimensional view types, which are emitted by create-array-slice-macro-call --view-types.
+#[path = "../templates/nd_view_types.rs"]
+mod nd_view_types;
+
+use nd_view_types::{MjArrayView, MjArrayViewMut};
+
+
+#[test]
+fn matrix_view() {
+    let data = [0, 1, 2, 3, 4, 5];
+    let view = MjArrayView::new(&data, [2, 3]);
+    assert_eq!((view.nrows(), view.ncols(), view.strides()), (2, 3, [3, 1]));
+    assert_eq!(view.row(1), &[3, 4, 5]);
+    assert_eq!(view.rows().collect::<Vec<_>>(), [&[0, 1, 2][..], &[3, 4, 5]]);
+    assert_eq!(view.col(2).copied().collect::<Vec<_>>(), [2, 5]);
+    assert_eq!(view.get([1, 2]), Some(&5));
+    assert_eq!(view.get([2, 0]), None);
+}
+
+
+#[test]
+fn matrix_view_without_rows() {
+    // E.g., efc_J when there are no constraints (nefc == 0)
+    let view = MjArrayView::<f64, 2>::new(&[], [0, 3]);
+    assert_eq!(view.rows().count(), 0);
+    assert_eq!(view.col(0).count(), 0);
+    assert_eq!(view.col(2).count(), 0);
+
+    let mut view = MjArrayViewMut::<f64, 2>::new(&mut [], [0, 3]);
+    assert_eq!(view.col_mut(2).count(), 0);
+}
+
+
+#[test]
+fn matrix_view_mut() {
+    let mut data = [0, 1, 2, 3, 4, 5];
+    let mut view = MjArrayViewMut::new(&mut data, [2, 3]);
+    assert_eq!((view.shape(), view.strides()), ([2, 3], [3, 1]));
+    view.row_mut(0).fill(7);
+    view.col_mut(1).for_each(|value| *value = 8);
+    *view.get_mut([1, 2]).unwrap() = 9;
+    assert_eq!(view.as_view().as_flat(), &[7, 8, 7, 3, 8, 9]);
+}
+
+
+#[test]
+fn three_dimensional_view() {
+    let data: Vec<_> = (0..12).collect();
+    let view = MjArrayView::new(&data, [2, 2, 3]);
+    let matrix = view.matrix(1);
+    assert_eq!(matrix.shape(), [2, 3]);
+    assert_eq!(matrix.row(0), &[6, 7, 8]);
+    assert_eq!(matrix.col(0).copied().collect::<Vec<_>>(), [6, 9]);
+
+    // Matrices without rows
+    let view = MjArrayView::<i32, 3>::new(&[], [2, 0, 3]);
+    assert_eq!(view.matrix(1).col(2).count(), 0);
+
+    let mut data = data;
+    let mut view = MjArrayViewMut::new(&mut data, [2, 2, 3]);
+    view.matrix_mut(0).row_mut(1).fill(0);
+    assert_eq!(&data[..6], &[0, 1, 2, 0, 0, 0]);
+}
+
+
+#[test]
+#[should_panic(expected = "column index out of bounds")]
+fn column_out_of_bounds() {
+    MjArrayView::new(&[0; 6], [2, 3]).col(3).count();
+}